@@ -54,7 +54,7 @@ impl ApplicationHandler for App {
             &drawing.queue,
             include_bytes!("they.webp"),
             "they",
-        ));
+        ).unwrap());
 
         let mut rows = Element::new().with_label("rows");
         let row1 = Element::new().with_label("row1");
@@ -201,7 +201,7 @@ impl ApplicationHandler for App {
                     this.drag = None;
                 }
                 Messages::MouseMove => {
-                    let pos = if let WindowEvent::MouseMove { position, .. } = message.window_event {
+                    let pos = if let Some(WindowEvent::MouseMove { position, .. }) = message.window_event {
                         position
                     } else {
                         continue;
@@ -59,7 +59,7 @@ impl ApplicationHandler for App {
             &drawing.queue,
             include_bytes!("they.webp"),
             "they",
-        ));
+        ).unwrap());
 
         let mut rows = Element::new().with_label("Hello");
         let styles = &mut rows.styles;
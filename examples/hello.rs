@@ -47,7 +47,7 @@ impl ApplicationHandler for App {
         let drawing = pollster::block_on(Drawing::new(window.clone()));
         window.set_visible(true);
 
-        let texture = load_texture_from_memory(&drawing.device, &drawing.queue, include_bytes!("they.webp"));
+        let texture = load_texture_from_memory(&drawing.device, &drawing.queue, include_bytes!("they.webp")).unwrap();
         
         let size = window.inner_size();
         let mut gui = Gui::new(size.into(), &drawing.device, &drawing.queue);
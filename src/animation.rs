@@ -0,0 +1,492 @@
+//! Style transitions and keyframe animations
+//!
+//! Time is driven by `Gui::advance`
+
+use crate::styles::styles_proposition::{Colors, Container, Theme, Themed, Values, ViewPort};
+
+/// Animatable property of an `Element`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Property {
+    Width,
+    Height,
+    /// Center of the `Element`
+    Position,
+    /// Offset added to the position of the `Element`
+    ///
+    /// Can only be animated using keyframes
+    Offset,
+    Rotation,
+    BgColor,
+    TextColor,
+    Alpha,
+    EdgesRadius,
+    EdgesSmooth,
+    TextSize,
+}
+
+/// Easing curve of a transition or animation
+#[derive(Debug, Clone, PartialEq, Copy, Default)]
+pub enum Easing {
+    #[default]
+    Linear,
+    EaseIn,
+    EaseOut,
+    EaseInOut,
+    /// Overshoots the target before settling
+    EaseOutBack,
+    /// CSS-like cubic bezier curve with control points `(x1, y1)` and `(x2, y2)`
+    CubicBezier(f32, f32, f32, f32),
+}
+
+impl Easing {
+    /// Maps linear progress in range `0..=1` onto the curve
+    pub fn apply(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            Easing::Linear => t,
+            Easing::EaseIn => t * t * t,
+            Easing::EaseOut => 1.0 - (1.0 - t).powi(3),
+            Easing::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (-2.0 * t + 2.0).powi(3) / 2.0
+                }
+            }
+            Easing::EaseOutBack => {
+                let c1 = 1.70158;
+                let c3 = c1 + 1.0;
+                1.0 + c3 * (t - 1.0).powi(3) + c1 * (t - 1.0).powi(2)
+            }
+            Easing::CubicBezier(x1, y1, x2, y2) => {
+                let bezier = |a: f32, b: f32, t: f32| {
+                    3.0 * a * t * (1.0 - t).powi(2) + 3.0 * b * t * t * (1.0 - t) + t * t * t
+                };
+                // find the curve parameter for `t` on the x axis
+                let mut s = t;
+                for _ in 0..8 {
+                    let x = bezier(*x1, *x2, s) - t;
+                    let dx = 3.0 * x1 * (1.0 - s).powi(2)
+                        + 6.0 * (x2 - x1) * s * (1.0 - s)
+                        + 3.0 * (1.0 - x2) * s * s;
+                    if dx.abs() < 1e-6 {
+                        break;
+                    }
+                    s = (s - x / dx).clamp(0.0, 1.0);
+                }
+                bezier(*y1, *y2, s)
+            }
+        }
+    }
+}
+
+/// Describes how a property changes when its style changes
+#[derive(Debug, Clone, PartialEq, Copy)]
+pub struct Transition {
+    /// Duration in seconds
+    pub duration: f32,
+    pub easing: Easing,
+}
+
+impl Transition {
+    pub fn new(duration: f32, easing: Easing) -> Self {
+        Self { duration, easing }
+    }
+}
+
+/// Transitions for each animatable property
+///
+/// Properties without a transition change instantly
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Transitions {
+    pub width: Option<Transition>,
+    pub height: Option<Transition>,
+    pub position: Option<Transition>,
+    pub rotation: Option<Transition>,
+    pub bg_color: Option<Transition>,
+    pub text_color: Option<Transition>,
+    pub alpha: Option<Transition>,
+    pub edges_radius: Option<Transition>,
+    pub edges_smooth: Option<Transition>,
+    pub text_size: Option<Transition>,
+}
+
+impl Transitions {
+    /// Applies the same transition to all properties
+    pub fn all(transition: Transition) -> Self {
+        Self {
+            width: Some(transition),
+            height: Some(transition),
+            position: Some(transition),
+            rotation: Some(transition),
+            bg_color: Some(transition),
+            text_color: Some(transition),
+            alpha: Some(transition),
+            edges_radius: Some(transition),
+            edges_smooth: Some(transition),
+            text_size: Some(transition),
+        }
+    }
+
+    pub fn get(&self, property: Property) -> Option<&Transition> {
+        match property {
+            Property::Width => self.width.as_ref(),
+            Property::Height => self.height.as_ref(),
+            Property::Position => self.position.as_ref(),
+            Property::Offset => None,
+            Property::Rotation => self.rotation.as_ref(),
+            Property::BgColor => self.bg_color.as_ref(),
+            Property::TextColor => self.text_color.as_ref(),
+            Property::Alpha => self.alpha.as_ref(),
+            Property::EdgesRadius => self.edges_radius.as_ref(),
+            Property::EdgesSmooth => self.edges_smooth.as_ref(),
+            Property::TextSize => self.text_size.as_ref(),
+        }
+    }
+}
+
+/// Value of a keyframe
+///
+/// References to the `Theme` are resolved each time the value is sampled,
+/// so running animations follow a switch of the theme
+#[derive(Debug, Clone)]
+pub enum AnimatedValue {
+    /// Size value, used for width, height, edges and text size
+    Values(Values),
+    /// Plain number, used for alpha and rotation in degrees
+    Number(f32),
+    /// Used for background and text color
+    Color(Colors),
+    /// Offset in x and y, used for position offset
+    Offset(Values, Values),
+}
+
+impl AnimatedValue {
+    pub(crate) fn channels(
+        &self,
+        container: &Container,
+        view_port: &ViewPort,
+        theme: &Theme,
+    ) -> Channels {
+        let calc = |values: &Values| {
+            themed(values, theme, |values| values.calc(container, view_port))
+        };
+        match self {
+            AnimatedValue::Values(values) => [calc(values), 0.0, 0.0, 0.0],
+            AnimatedValue::Number(number) => [*number, 0.0, 0.0, 0.0],
+            AnimatedValue::Color(color) => themed(color, theme, |color| color.to_rgba().into()),
+            AnimatedValue::Offset(x, y) => [calc(x), calc(y), 0.0, 0.0],
+        }
+    }
+}
+
+/// Calls `f` with the value after resolving its references to the `theme`
+fn themed<T: Themed, R>(value: &T, theme: &Theme, f: impl FnOnce(&T) -> R) -> R {
+    match value.resolve(theme) {
+        Some(value) => f(&value),
+        None => f(value),
+    }
+}
+
+/// Point in time of an `Animation`
+#[derive(Debug, Clone)]
+pub struct Keyframe {
+    /// Time in range `0..=1` relative to the duration of the animation
+    pub time: f32,
+    pub value: AnimatedValue,
+}
+
+/// How many times an `Animation` plays
+#[derive(Debug, Clone, Copy, Default)]
+pub enum Repeat {
+    #[default]
+    Once,
+    Times(u32),
+    Forever,
+}
+
+/// Keyframe animation of a single property
+///
+/// Animated values take precedence over styles while the animation is playing
+#[derive(Debug, Clone)]
+pub struct Animation {
+    /// Name reported when the animation ends
+    pub name: Option<String>,
+    pub property: Property,
+    pub keyframes: Vec<Keyframe>,
+    /// Duration of one iteration in seconds
+    pub duration: f32,
+    /// Easing applied between each pair of keyframes
+    pub easing: Easing,
+    pub repeat: Repeat,
+    /// Keeps the last keyframe applied after the animation ends
+    pub fill: bool,
+}
+
+impl Animation {
+    pub fn new(property: Property, duration: f32) -> Self {
+        Self {
+            name: None,
+            property,
+            keyframes: Vec::new(),
+            duration,
+            easing: Easing::Linear,
+            repeat: Repeat::Once,
+            fill: false,
+        }
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    /// Adds a keyframe at `time` in range `0..=1`
+    pub fn keyframe(mut self, time: f32, value: AnimatedValue) -> Self {
+        let index = self
+            .keyframes
+            .iter()
+            .position(|keyframe| keyframe.time > time)
+            .unwrap_or(self.keyframes.len());
+        self.keyframes.insert(index, Keyframe { time, value });
+        self
+    }
+
+    pub fn with_easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    pub fn with_repeat(mut self, repeat: Repeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    pub fn with_fill(mut self, fill: bool) -> Self {
+        self.fill = fill;
+        self
+    }
+
+    fn total_duration(&self) -> f32 {
+        match self.repeat {
+            Repeat::Once => self.duration,
+            Repeat::Times(times) => self.duration * times as f32,
+            Repeat::Forever => f32::INFINITY,
+        }
+    }
+
+    fn sample(
+        &self,
+        progress: f32,
+        resolve: &dyn Fn(&AnimatedValue) -> Channels,
+    ) -> Option<Channels> {
+        let first = self.keyframes.first()?;
+        if progress <= first.time {
+            return Some(resolve(&first.value));
+        }
+        for pair in self.keyframes.windows(2) {
+            let (from, to) = (&pair[0], &pair[1]);
+            if progress <= to.time {
+                let span = to.time - from.time;
+                let t = if span <= 0.0 {
+                    1.0
+                } else {
+                    (progress - from.time) / span
+                };
+                return Some(lerp(
+                    resolve(&from.value),
+                    resolve(&to.value),
+                    self.easing.apply(t),
+                ));
+            }
+        }
+        self.keyframes.last().map(|last| resolve(&last.value))
+    }
+}
+
+/// Values of a property packed into four channels
+pub(crate) type Channels = [f32; 4];
+
+fn lerp(from: Channels, to: Channels, t: f32) -> Channels {
+    [
+        from[0] + (to[0] - from[0]) * t,
+        from[1] + (to[1] - from[1]) * t,
+        from[2] + (to[2] - from[2]) * t,
+        from[3] + (to[3] - from[3]) * t,
+    ]
+}
+
+/// Animation or transition that has ended
+pub(crate) enum Finished {
+    Transition(Property),
+    Animation(Option<String>),
+}
+
+struct Tween {
+    property: Property,
+    from: Channels,
+    to: Channels,
+    elapsed: f32,
+    transition: Option<Transition>,
+}
+
+impl Tween {
+    fn current(&self) -> Channels {
+        match &self.transition {
+            Some(transition) if transition.duration > 0.0 => lerp(
+                self.from,
+                self.to,
+                transition
+                    .easing
+                    .apply(self.elapsed / transition.duration),
+            ),
+            _ => self.to,
+        }
+    }
+}
+
+struct Playing {
+    animation: Animation,
+    elapsed: f32,
+    finished: bool,
+}
+
+impl Playing {
+    fn progress(&self) -> f32 {
+        if self.finished || self.animation.duration <= 0.0 {
+            return 1.0;
+        }
+        (self.elapsed % self.animation.duration) / self.animation.duration
+    }
+}
+
+/// Running transitions and animations of an `Element`
+#[derive(Default)]
+pub(crate) struct Animator {
+    tweens: Vec<Tween>,
+    animations: Vec<Playing>,
+}
+
+impl Animator {
+    /// Returns the displayed value of a property whose style resolves to `target`
+    pub fn apply(
+        &mut self,
+        property: Property,
+        target: Channels,
+        transitions: &Transitions,
+        resolve: &dyn Fn(&AnimatedValue) -> Channels,
+    ) -> Channels {
+        let value = self.transition(property, target, transitions.get(property));
+        self.sample(property, resolve).unwrap_or(value)
+    }
+
+    /// Same as `apply` for properties with a single value
+    pub fn apply_scalar(
+        &mut self,
+        property: Property,
+        target: f32,
+        transitions: &Transitions,
+        resolve: &dyn Fn(&AnimatedValue) -> Channels,
+    ) -> f32 {
+        self.apply(property, [target, 0.0, 0.0, 0.0], transitions, resolve)[0]
+    }
+
+    fn transition(
+        &mut self,
+        property: Property,
+        target: Channels,
+        transition: Option<&Transition>,
+    ) -> Channels {
+        let tween = match self.tweens.iter_mut().find(|t| t.property == property) {
+            Some(tween) => tween,
+            None => {
+                self.tweens.push(Tween {
+                    property,
+                    from: target,
+                    to: target,
+                    elapsed: 0.0,
+                    transition: None,
+                });
+                return target;
+            }
+        };
+        if tween.to != target {
+            tween.from = match transition {
+                Some(_) => tween.current(),
+                None => target,
+            };
+            tween.to = target;
+            tween.elapsed = 0.0;
+            tween.transition = transition.copied();
+        }
+        tween.current()
+    }
+
+    /// Returns the animated value of a property if it is being animated
+    pub fn sample(
+        &self,
+        property: Property,
+        resolve: &dyn Fn(&AnimatedValue) -> Channels,
+    ) -> Option<Channels> {
+        self.animations
+            .iter()
+            .rev()
+            .find(|playing| playing.animation.property == property)
+            .and_then(|playing| playing.animation.sample(playing.progress(), resolve))
+    }
+
+    pub fn play(&mut self, animation: Animation) {
+        self.animations
+            .retain(|playing| !(playing.finished && playing.animation.property == animation.property));
+        self.animations.push(Playing {
+            animation,
+            elapsed: 0.0,
+            finished: false,
+        });
+    }
+
+    pub fn stop(&mut self, name: Option<&str>) {
+        match name {
+            Some(name) => self
+                .animations
+                .retain(|playing| playing.animation.name.as_deref() != Some(name)),
+            None => self.animations.clear(),
+        }
+    }
+
+    pub fn is_animating(&self) -> bool {
+        self.animations.iter().any(|playing| !playing.finished)
+            || self
+                .tweens
+                .iter()
+                .any(|tween| tween.transition.is_some())
+    }
+
+    pub fn advance(&mut self, delta: f32) -> Vec<Finished> {
+        let mut finished = Vec::new();
+        for tween in &mut self.tweens {
+            let duration = match &tween.transition {
+                Some(transition) => transition.duration,
+                None => continue,
+            };
+            tween.elapsed += delta;
+            if tween.elapsed >= duration {
+                tween.transition = None;
+                finished.push(Finished::Transition(tween.property));
+            }
+        }
+        for playing in &mut self.animations {
+            if playing.finished {
+                continue;
+            }
+            playing.elapsed += delta;
+            if playing.elapsed >= playing.animation.total_duration() {
+                playing.finished = true;
+                finished.push(Finished::Animation(playing.animation.name.clone()));
+            }
+        }
+        self.animations
+            .retain(|playing| !playing.finished || playing.animation.fill);
+        finished
+    }
+}
@@ -0,0 +1,79 @@
+//! Errors of fallible operations, mostly loading of textures
+
+use std::fmt;
+
+/// Error produced while loading a texture or a sprite sheet
+#[derive(Debug)]
+pub enum Error {
+    /// File could not be read
+    Io(std::io::Error),
+    /// Image could not be decoded
+    Image(image::ImageError),
+    /// Raw pixel data does not match the width and height
+    DataLength { expected: usize, found: usize },
+    /// Image is empty or larger than the limit of the device or atlas page
+    Size { width: u32, height: u32, max: u32 },
+    /// Sprite sheet description is not valid JSON
+    #[cfg(feature = "json")]
+    Json(serde_json::Error),
+    /// Sprite sheet description is missing a field
+    #[cfg(feature = "json")]
+    SpriteSheet(String),
+}
+
+/// Result with `rugui::Error` as the default error
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "could not read file: {}", err),
+            Error::Image(err) => write!(f, "could not decode image: {}", err),
+            Error::DataLength { expected, found } => write!(
+                f,
+                "expected {} bytes of pixel data, found {}",
+                expected, found
+            ),
+            Error::Size { width, height, max } => write!(
+                f,
+                "image of {}x{} pixels does not fit the limit of {}",
+                width, height, max
+            ),
+            #[cfg(feature = "json")]
+            Error::Json(err) => write!(f, "could not parse sprite sheet: {}", err),
+            #[cfg(feature = "json")]
+            Error::SpriteSheet(message) => write!(f, "invalid sprite sheet: {}", message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            Error::Image(err) => Some(err),
+            #[cfg(feature = "json")]
+            Error::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for Error {
+    fn from(err: std::io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<image::ImageError> for Error {
+    fn from(err: image::ImageError) -> Self {
+        Error::Image(err)
+    }
+}
+
+#[cfg(feature = "json")]
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
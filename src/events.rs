@@ -1,6 +1,6 @@
 //! Event data types
 
-use crate::{Element, ElementKey, InputState, Point};
+use crate::{animation::Property, widgets::WidgetValue, Element, ElementKey, InputState, Point};
 
 #[derive(Debug, Clone)]
 pub enum MouseButton {
@@ -37,6 +37,10 @@ pub enum WindowEvent {
     },
     SelectNext,
     SelectPrev,
+    /// Key pressed while holding control, handled by menus, see `menu::Shortcut`
+    Shortcut {
+        key: char,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -67,8 +71,33 @@ pub enum ElementEvent {
     Input {
         text: String,
     },
+    /// Key pressed while holding control, see `WindowEvent::Shortcut`
+    Shortcut {
+        key: char,
+    },
     Select,
     Unselect,
+    /// A keyframe animation has ended
+    AnimationEnd {
+        name: Option<String>,
+    },
+    /// A style transition has ended
+    TransitionEnd {
+        property: Property,
+    },
+    /// A button widget was clicked
+    Click,
+    /// Value of a widget was changed by the user
+    Change {
+        value: WidgetValue,
+    },
+    /// Size of the `Element` changed, in physical pixels
+    ///
+    /// Render to a `texture::Texture::render_target` at this resolution
+    Resize {
+        width: u32,
+        height: u32,
+    },
 }
 
 impl ElementEvent {
@@ -97,7 +126,7 @@ impl ElementEvent {
             WindowEvent::Input { text } => ElementEvent::Input { text: text.clone() },
             WindowEvent::SelectNext => unreachable!("ble ble contact the developer"),
             WindowEvent::SelectPrev => unreachable!("ble ble contact the developer"),
-            
+            WindowEvent::Shortcut { key } => ElementEvent::Shortcut { key: *key },
         }
     }
 }
@@ -111,7 +140,13 @@ pub enum EventTypes {
     MouseLeave,
     Scroll,
     Input,
+    Shortcut,
     Select,
+    AnimationEnd,
+    TransitionEnd,
+    Click,
+    Change,
+    Resize,
 }
 
 impl From<WindowEvent> for EventTypes {
@@ -124,6 +159,7 @@ impl From<WindowEvent> for EventTypes {
             WindowEvent::Scroll { .. } => EventTypes::Scroll,
             WindowEvent::SelectNext => EventTypes::Select,
             WindowEvent::SelectPrev => EventTypes::Select,
+            WindowEvent::Shortcut { .. } => EventTypes::Shortcut,
         }
     }
 }
@@ -149,7 +185,10 @@ where
     Msg: Clone,
 {
     pub event_type: EventTypes,
-    pub window_event: WindowEvent,
+    /// Window event that caused this event
+    ///
+    /// `None` for events caused by the `Gui` itself, like ended animations
+    pub window_event: Option<WindowEvent>,
     pub element_event: ElementEvent,
     pub msg: Msg,
     pub key: ElementKey,
@@ -3,20 +3,42 @@
 //! ## Feature flags
 #![doc = document_features::document_features!(feature_label = r#"<span class="stab portability"><code>{feature}</code></span>"#)]
 
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
 
+use animation::{AnimatedValue, Animation, Animator, Finished, Property};
 #[cfg(feature = "clipboard")]
 use clipboard::{ClipboardContext, ClipboardProvider};
 use events::{ElementEvent, EventPoll, EventTypes, WindowEvent};
-use cosmic_text::{Attrs, FontSystem, Metrics, SwashCache};
+use cosmic_text::{Attrs, Family, FontSystem, Metrics, SwashCache};
 use image::{DynamicImage, GenericImage};
-use render::{GpuBound, RenderElement, RenderElementData, RenderLinearGradient, RenderRadialGradient};
-use styles::styles_proposition::{Container, Side, Styles, Values, ViewPort};
-
+use widgets::Widget;
+use menu::MenuState;
+use tooltip::{Tooltip, TooltipContent, TooltipState};
+use render::{
+    GpuBound, RenderElement, RenderElementData, RenderLinearGradient, RenderRadialGradient,
+    TextureParams,
+};
+use styles::styles_proposition::{
+    Container, ElementState, Inherited, Parent, Position, Rectangle, Round, Side, StyleSheet, Styles,
+    Theme, Values, ViewPort,
+};
+use texture::{AnimatedTexture, Playback, Tiling};
+
+pub use error::{Error, Result};
+
+pub mod animation;
+pub mod error;
 pub mod events;
+pub mod menu;
 mod render;
+pub mod parser;
 pub mod styles;
 pub mod texture;
+pub mod tooltip;
+pub mod widgets;
 #[cfg(feature = "winit")]
 pub mod winit;
 
@@ -29,7 +51,11 @@ where
 {
     elements: HashMap<ElementKey, Element<Msg>>,
     events: EventPoll<Msg>,
-    entry: Option<ElementKey>,
+    layers: BTreeMap<Layer, ElementKey>,
+    /// Index into `ordered` of the first `Element` that receives input
+    input_start: usize,
+    /// Set while laying out layers beneath a modal
+    input_blocked: bool,
     last_key: u64,
     size: (u32, u32),
     gpu: GpuBound,
@@ -38,6 +64,26 @@ where
     swash_cache: Option<SwashCache>,
     select: Select,
     ordered: Vec<ElementKey>,
+    style_sheet: StyleSheet,
+    /// Default styles of widgets and menus, the `style_sheet` takes precedence
+    base_sheet: StyleSheet,
+    theme: Theme,
+    theme_dirty: bool,
+    time: f32,
+    round: Round,
+    scale_factor: f32,
+    resized: bool,
+    overlay_queue: Vec<(ElementKey, ElementTransform)>,
+    tooltip: TooltipState,
+    /// Range of `ordered` taken by `Layer::Tooltip`, tooltips can not be hovered
+    tooltip_range: (usize, usize),
+    /// Widget that was pressed, see `Gui::widget_event`
+    active_widget: Option<ElementKey>,
+    /// Dropdown whose list is shown in `Layer::Popup`
+    open_dropdown: Option<ElementKey>,
+    /// Widget owning each row of dropdowns and list boxes
+    row_owners: HashMap<ElementKey, ElementKey>,
+    menus: MenuState<Msg>,
     #[cfg(feature = "clipboard")]
     clipboard_ctx: Option<ClipboardContext>,
 }
@@ -46,6 +92,7 @@ struct InputState {
     pub(crate) mouse: Point,
     pub(crate) prev_mouse: Point,
     pub(crate) hover: Option<ElementKey>,
+    pub(crate) pressed: Option<ElementKey>,
     pub(crate) control_pressed: bool,
 }
 
@@ -69,6 +116,7 @@ impl InputState {
             mouse: Point::new(0.0, 0.0),
             prev_mouse: Point::new(0.0, 0.0),
             hover: None,
+            pressed: None,
             control_pressed: false,
         }
     }
@@ -93,7 +141,9 @@ where
                 queue: Vec::new(),
             },
             last_key: 0,
-            entry: None,
+            layers: BTreeMap::new(),
+            input_start: 0,
+            input_blocked: false,
             size,
             gpu,
             input: InputState::new(),
@@ -101,6 +151,21 @@ where
             swash_cache: Some(SwashCache::new()),
             select: Select::new(),
             ordered: Vec::new(),
+            style_sheet: StyleSheet::new(),
+            base_sheet: StyleSheet::new(),
+            theme: Theme::light(),
+            theme_dirty: false,
+            time: 0.0,
+            round: Round::None,
+            scale_factor: 1.0,
+            resized: false,
+            overlay_queue: Vec::new(),
+            tooltip: TooltipState::default(),
+            tooltip_range: (0, 0),
+            active_widget: None,
+            open_dropdown: None,
+            row_owners: HashMap::new(),
+            menus: MenuState::default(),
             #[cfg(feature = "clipboard")]
             clipboard_ctx: ClipboardContext::new().ok(),
         };
@@ -120,6 +185,7 @@ where
 
     pub fn remove_element(&mut self, key: ElementKey) {
         self.elements.remove(&key);
+        self.row_owners.remove(&key);
     }
 
     pub fn get_element(&self, key: ElementKey) -> Option<&Element<Msg>> {
@@ -130,26 +196,292 @@ where
         self.elements.get_mut(&key).map(|element| element)
     }
 
+    /// Sets the root of `Layer::Base`, the previous root is removed
     pub fn set_entry(&mut self, key: Option<ElementKey>) {
-        if let Some(entry) = self.entry.take() {
+        if let Some(entry) = self.set_layer(Layer::Base, key) {
             self.remove_element(entry);
         }
-        self.entry = key;
+    }
+
+    /// Sets the root `Element` of a layer and returns the previous one
+    ///
+    /// Unlike `Gui::set_entry` the previous root is not removed
+    pub fn set_layer(&mut self, layer: Layer, key: Option<ElementKey>) -> Option<ElementKey> {
+        if layer.is_modal() && key.is_some() {
+            // popups of the layers beneath would cover the modal
+            self.close_dropdown();
+            self.close_menus();
+        }
+        let previous = match key {
+            Some(key) => self.layers.insert(layer, key),
+            None => self.layers.remove(&layer),
+        };
         if let Some(key) = key {
-            let transform = ElementTransform {
-                position: Point::new(self.size.0 as f32 / 2.0, self.size.1 as f32 / 2.0),
-                scale: Point::new(self.size.0 as f32, self.size.1 as f32),
-                rotation: 0.0,
-            };
-            self.element_transform(key, &transform);
+            self.layout(key);
+        }
+        previous
+    }
+
+    /// Returns the root `Element` of a layer
+    pub fn layer(&self, layer: Layer) -> Option<ElementKey> {
+        self.layers.get(&layer).copied()
+    }
+
+    /// Registers or replaces a style class
+    pub fn set_class(&mut self, name: &str, styles: Styles) {
+        self.style_sheet.set_class(name, styles);
+    }
+
+    /// Returns a style class for modification
+    ///
+    /// `Elements` using the class will be restyled on the next update
+    pub fn class_mut(&mut self, name: &str) -> Option<&mut Styles> {
+        self.style_sheet.class_mut(name)
+    }
+
+    pub fn remove_class(&mut self, name: &str) -> Option<Styles> {
+        self.style_sheet.remove_class(name)
+    }
+
+    pub fn style_sheet(&self) -> &StyleSheet {
+        &self.style_sheet
+    }
+
+    pub fn style_sheet_mut(&mut self) -> &mut StyleSheet {
+        &mut self.style_sheet
+    }
+
+    /// Replaces the `StyleSheet`
+    ///
+    /// All `Elements` will be restyled on the next update
+    pub fn set_style_sheet(&mut self, style_sheet: StyleSheet) {
+        self.style_sheet = style_sheet;
+        self.style_sheet.dirty = true;
+    }
+
+    /// Adds a default class of widgets or menus unless it was already added
+    ///
+    /// Defaults live below the `StyleSheet`, so replacing the sheet keeps them
+    pub(crate) fn set_default_class(&mut self, name: &str, styles: Styles) {
+        if self.base_sheet.get_class(name).is_none() {
+            self.base_sheet.set_class(name, styles);
+        }
+    }
+
+    /// Switches the `Theme` referenced by styles
+    ///
+    /// `Elements` referencing the theme will be restyled on the next update
+    pub fn set_theme(&mut self, theme: Theme) {
+        self.theme = theme;
+        self.theme_dirty = true;
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
+    }
+
+    /// Returns the current `Theme` for modification
+    ///
+    /// `Elements` referencing the theme will be restyled on the next update
+    pub fn theme_mut(&mut self) -> &mut Theme {
+        self.theme_dirty = true;
+        &mut self.theme
+    }
+
+    /// Advances the clock of transitions, animations and animated textures by `delta` seconds
+    ///
+    /// Call this once every frame. Ended transitions and animations are
+    /// reported to `TransitionEnd` and `AnimationEnd` listeners.
+    pub fn advance(&mut self, delta: f32) {
+        self.time += delta;
+        for (key, element) in self.elements.iter_mut() {
+            if let Some(frame) = element.playback.as_mut().and_then(|playback| playback.advance(delta)) {
+                element.styles.set_region(&frame);
+            }
+            for finished in element.animator.advance(delta) {
+                let (event_type, element_event) = match finished {
+                    Finished::Transition(property) => (
+                        EventTypes::TransitionEnd,
+                        ElementEvent::TransitionEnd { property },
+                    ),
+                    Finished::Animation(name) => {
+                        (EventTypes::AnimationEnd, ElementEvent::AnimationEnd { name })
+                    }
+                };
+                if let Some(listeners) = element.events.get(&event_type) {
+                    for EventListener { msg, .. } in listeners {
+                        self.events.events.push(events::Event {
+                            event_type,
+                            window_event: None,
+                            element_event: element_event.clone(),
+                            msg,
+                            key: *key,
+                        });
+                    }
+                }
+            }
+        }
+        self.advance_tooltip();
+    }
+
+    /// Shows the tooltip of the hovered `Element` once its delay has passed
+    fn advance_tooltip(&mut self) {
+        let target = match (self.tooltip.target, self.tooltip.shown, self.tooltip.dismissed) {
+            (Some(target), None, false) => target,
+            _ => return,
+        };
+        let tooltip = match self.get_element(target).and_then(|e| e.tooltip.clone()) {
+            Some(tooltip) => tooltip,
+            None => {
+                self.tooltip.target = None;
+                return;
+            }
+        };
+        if self.time - self.tooltip.since < tooltip.delay {
+            return;
+        }
+        let (key, owned) = match tooltip.content {
+            TooltipContent::Element(key) => (key, false),
+            TooltipContent::Text(text) => {
+                self.default_tooltip_class();
+                let mut element = Element::new().with_class("tooltip");
+                element.set_text(Some(text));
+                (self.add_element(element), true)
+            }
+        };
+        self.tooltip.shown = Some(key);
+        self.tooltip.owned = owned;
+        self.tooltip.cursor = self.input.mouse;
+        self.set_layer(Layer::Tooltip, Some(key));
+    }
+
+    /// Hides the shown tooltip
+    ///
+    /// With `dismiss` the tooltip is not shown again until the target is entered again
+    fn hide_tooltip(&mut self, dismiss: bool) {
+        self.tooltip.dismissed |= dismiss;
+        let shown = match self.tooltip.shown.take() {
+            Some(shown) => shown,
+            None => return,
+        };
+        if self.layer(Layer::Tooltip) == Some(shown) {
+            self.set_layer(Layer::Tooltip, None);
+        }
+        if self.tooltip.owned {
+            self.remove_element(shown);
         }
     }
 
+    /// Finds the topmost `Element` with a tooltip under the cursor
+    fn fix_tooltip(&mut self) {
+        let target = self.find_under_cursor(|e| e.tooltip.is_some());
+        if target == self.tooltip.target {
+            return;
+        }
+        self.hide_tooltip(false);
+        self.tooltip.target = target;
+        self.tooltip.since = self.time;
+        self.tooltip.dismissed = false;
+    }
+
+    /// Finds the topmost enabled `Element` under the cursor that matches `filter`
+    ///
+    /// `Elements` beneath a modal and tooltips are ignored
+    pub(crate) fn find_under_cursor(
+        &self,
+        filter: impl Fn(&Element<Msg>) -> bool,
+    ) -> Option<ElementKey> {
+        self.ordered
+            .iter()
+            .enumerate()
+            .skip(self.input_start)
+            .rev()
+            .filter(|(i, _)| !self.in_tooltip(*i))
+            .filter_map(|(_, key)| self.get_element(*key).map(|e| (*key, e)))
+            .find(|(_, e)| {
+                filter(e) && !e.styles.disabled && e.transform.point_collision(self.input.mouse)
+            })
+            .map(|(key, _)| key)
+    }
+
+    fn in_tooltip(&self, index: usize) -> bool {
+        index >= self.tooltip_range.0 && index < self.tooltip_range.1
+    }
+
+    /// Moves the shown tooltip next to its target
+    fn place_tooltip(&mut self) {
+        let (shown, target) = match (self.tooltip.shown, self.tooltip.target) {
+            (Some(shown), Some(target)) => (shown, target),
+            _ => return,
+        };
+        let (target_transform, placement) = match self.get_element(target) {
+            Some(e) => match &e.tooltip {
+                Some(tooltip) => (e.transform.clone(), tooltip.placement),
+                None => return,
+            },
+            None => return,
+        };
+        let size = match self.get_element(shown) {
+            Some(e) => e.transform.scale,
+            None => return,
+        };
+        let view_port = Point::new(self.size.0 as f32, self.size.1 as f32);
+        let gap = 8.0 * self.scale_factor;
+        let center =
+            placement.place(&target_transform, size, self.tooltip.cursor, view_port, gap);
+        if let Some(e) = self.get_element_mut(shown) {
+            e.styles.position.set(
+                Position::top_left()
+                    .in_view_port()
+                    .with_offset(center.x, center.y),
+            );
+        }
+        self.layout(shown);
+    }
+
+    /// Returns time in seconds accumulated by `Gui::advance`
+    pub fn time(&self) -> f32 {
+        self.time
+    }
+
+    /// Sets the rounding of `Elements` that do not set their own
+    pub fn set_round(&mut self, round: Round) {
+        self.round = round;
+    }
+
+    pub fn round(&self) -> Round {
+        self.round
+    }
+
+    /// Sets the ratio of physical to logical pixels
+    ///
+    /// `Value::Logical` is multiplied by it, the size of the `Gui` stays in physical pixels
+    pub fn set_scale_factor(&mut self, scale_factor: f32) {
+        self.scale_factor = scale_factor;
+    }
+
+    pub fn scale_factor(&self) -> f32 {
+        self.scale_factor
+    }
+
+    /// Sets the size in physical pixels without access to the `wgpu::Queue`
+    ///
+    /// The layout is updated on the next update and the gpu on the next `Gui::prepare`
+    pub fn set_size(&mut self, size: (u32, u32)) {
+        self.size = size;
+        self.resized = true;
+    }
+
     pub fn event(&mut self, event: events::WindowEvent) {
         self.events.queue.push(event);
     }
 
     pub fn select_element(&mut self, key: ElementKey, msg: Msg) {
+        if let Some(element) = self.get_element(key) {
+            if element.styles.disabled {
+                return;
+            }
+        }
         match self.select.selected {
             Some(selected) => {
                 if selected == key {
@@ -166,7 +498,7 @@ where
                 for EventListener { msg, .. } in listeners {
                     self.events.events.push(events::Event {
                         event_type: EventTypes::Select,
-                        window_event: WindowEvent::SelectNext,
+                        window_event: Some(WindowEvent::SelectNext),
                         element_event: ElementEvent::Unselect,
                         msg: msg.clone(),
                         key: selected,
@@ -178,7 +510,7 @@ where
         self.select.selected = Some(key);
         self.events.events.push(events::Event {
             event_type: EventTypes::Select,
-            window_event: WindowEvent::SelectNext,
+            window_event: Some(WindowEvent::SelectNext),
             element_event: ElementEvent::Select,
             msg,
             key,
@@ -190,13 +522,13 @@ where
         if self.input.hover != this_hover {
             match self.input.hover {
                 Some(key) => {
-                    if let Some(e) = self.get_element(key) {
+                    if let Some(e) = self.get_element(key).filter(|e| !e.styles.disabled) {
                         let element_event = ElementEvent::from_window_event(event, e, &self.input);
                         if let Some(listeners) = e.events.get(&EventTypes::MouseLeave) {
                             for EventListener { msg, .. } in listeners {
                                 self.events.events.push(events::Event {
                                     event_type: EventTypes::MouseLeave,
-                                    window_event: event.clone(),
+                                    window_event: Some(event.clone()),
                                     element_event: element_event.clone(),
                                     msg,
                                     key,
@@ -209,13 +541,13 @@ where
             }
             match this_hover {
                 Some(key) => {
-                    if let Some(e) = self.get_element(key) {
+                    if let Some(e) = self.get_element(key).filter(|e| !e.styles.disabled) {
                         let element_event = ElementEvent::from_window_event(&event, e, &self.input);
                         if let Some(listeners) = e.events.get(&EventTypes::MouseEnter) {
                             for EventListener { msg, .. } in listeners {
                                 self.events.events.push(events::Event {
                                     event_type: EventTypes::MouseEnter,
-                                    window_event: event.clone(),
+                                    window_event: Some(event.clone()),
                                     element_event: element_event.clone(),
                                     msg,
                                     key,
@@ -228,10 +560,14 @@ where
             }
         }
         self.input.hover = this_hover;
+        self.fix_tooltip();
     }
 
     fn find_hovered_element(&self) -> Option<ElementKey> {
-        for key in self.ordered.iter().rev() {
+        for (i, key) in self.ordered.iter().enumerate().skip(self.input_start).rev() {
+            if self.in_tooltip(i) {
+                continue;
+            }
             let element = if let Some(e) = self.get_element(*key) {
                 e
             } else {
@@ -251,7 +587,25 @@ where
                     self.input.prev_mouse = self.input.mouse;
                     self.input.mouse = *position;
 
-                    self.fix_hovers(&event)
+                    self.fix_hovers(&event);
+                    self.menu_event(&event);
+                    self.widget_event(&event);
+                }
+                WindowEvent::MouseDown { .. } => {
+                    self.input.pressed = self.input.hover;
+                    self.hide_tooltip(true);
+                    if self.menu_event(&event) {
+                        continue;
+                    }
+                    self.widget_event(&event);
+                }
+                WindowEvent::Scroll { .. } => {
+                    self.hide_tooltip(true);
+                    self.widget_event(&event);
+                }
+                WindowEvent::MouseUp { .. } => {
+                    self.input.pressed = None;
+                    self.widget_event(&event);
                 }
                 WindowEvent::SelectNext => {
                     match &self.select.selected {
@@ -275,7 +629,7 @@ where
                                         for EventListener { msg, .. } in listeners {
                                             self.events.events.push(events::Event {
                                                 event_type: EventTypes::Select,
-                                                window_event: WindowEvent::SelectNext,
+                                                window_event: Some(WindowEvent::SelectNext),
                                                 element_event: ElementEvent::Unselect,
                                                 msg,
                                                 key: *selected,
@@ -286,7 +640,7 @@ where
                                         for EventListener { msg, .. } in listeners {
                                             self.events.events.push(events::Event {
                                                 event_type: EventTypes::Select,
-                                                window_event: WindowEvent::SelectNext,
+                                                window_event: Some(WindowEvent::SelectNext),
                                                 element_event: ElementEvent::Unselect,
                                                 msg,
                                                 key: *selected,
@@ -306,7 +660,7 @@ where
                                         for EventListener { msg, .. } in listeners {
                                             self.events.events.push(events::Event {
                                                 event_type: EventTypes::Select,
-                                                window_event: WindowEvent::SelectNext,
+                                                window_event: Some(WindowEvent::SelectNext),
                                                 element_event: ElementEvent::Select,
                                                 msg,
                                                 key: self.select.selectables[i + 1],
@@ -319,7 +673,7 @@ where
                                         for EventListener { msg, .. } in listeners {
                                             self.events.events.push(events::Event {
                                                 event_type: EventTypes::Select,
-                                                window_event: WindowEvent::SelectNext,
+                                                window_event: Some(WindowEvent::SelectNext),
                                                 element_event: ElementEvent::Select,
                                                 msg,
                                                 key: *selected,
@@ -338,7 +692,7 @@ where
                                         for EventListener { msg, .. } in listeners {
                                             self.events.events.push(events::Event {
                                                 event_type: EventTypes::Select,
-                                                window_event: WindowEvent::SelectNext,
+                                                window_event: Some(WindowEvent::SelectNext),
                                                 element_event: ElementEvent::Select,
                                                 msg,
                                                 key: *key,
@@ -349,7 +703,7 @@ where
                                         for EventListener { msg, .. } in listeners {
                                             self.events.events.push(events::Event {
                                                 event_type: EventTypes::Select,
-                                                window_event: WindowEvent::SelectNext,
+                                                window_event: Some(WindowEvent::SelectNext),
                                                 element_event: ElementEvent::Unselect,
                                                 msg,
                                                 key: *selected,
@@ -374,7 +728,7 @@ where
                                 for EventListener { msg, .. } in listeners {
                                     self.events.events.push(events::Event {
                                         event_type: EventTypes::Select,
-                                        window_event: WindowEvent::SelectNext,
+                                        window_event: Some(WindowEvent::SelectNext),
                                         element_event: ElementEvent::Select,
                                         msg,
                                         key: *key,
@@ -386,19 +740,27 @@ where
                     }
                     return;
                 }
+                WindowEvent::Shortcut { .. } => {
+                    self.menu_event(&event);
+                    continue;
+                }
                 WindowEvent::Input { text } => {
+                    if self.menu_event(&event) {
+                        continue;
+                    }
                     let key = if let Some(key) = self.select.selected {
                         key
                     } else {
                         return;
                     };
-                    if let Some(e) = self.get_element(key) {
+                    self.widget_event(&event);
+                    if let Some(e) = self.get_element(key).filter(|e| !e.styles.disabled) {
                         match e.events.get(&EventTypes::Input) {
                             Some(e) => {
                                 for EventListener { msg, .. } in e {
                                     self.events.events.push(events::Event {
                                         event_type: EventTypes::Input,
-                                        window_event: event.clone(),
+                                        window_event: Some(event.clone()),
                                         element_event: ElementEvent::Input { text: text.clone() },
                                         msg,
                                         key,
@@ -414,12 +776,15 @@ where
             }
             //self.element_event(entry_key, &event);
             let mut consumed = false;
-            for i in (0..self.ordered.len()).rev() {
+            for i in (self.input_start..self.ordered.len()).rev() {
                 let element = if let Some(e) = self.get_element(self.ordered[i]) {
                     e
                 } else {
                     continue;
                 };
+                if element.styles.disabled {
+                    continue;
+                }
 
                 match &event {
                     // Events that need to take into account cursor position
@@ -448,7 +813,7 @@ where
                                                 }
                                                 self.events.events.push(events::Event {
                                                     event_type,
-                                                    window_event: event.clone(),
+                                                    window_event: Some(event.clone()),
                                                     element_event: element_event.clone(),
                                                     msg: msg.clone(),
                                                     key: self.ordered[i],
@@ -461,7 +826,7 @@ where
                                                 }
                                                 self.events.events.push(events::Event {
                                                     event_type,
-                                                    window_event: event.clone(),
+                                                    window_event: Some(event.clone()),
                                                     element_event: element_event.clone(),
                                                     msg: msg.clone(),
                                                     key: self.ordered[i],
@@ -470,7 +835,7 @@ where
                                             EventListenerTypes::Force => {
                                                 self.events.events.push(events::Event {
                                                     event_type,
-                                                    window_event: event.clone(),
+                                                    window_event: Some(event.clone()),
                                                     element_event: element_event.clone(),
                                                     msg: msg.clone(),
                                                     key: self.ordered[i],
@@ -487,17 +852,24 @@ where
                     WindowEvent::Input { .. } => (),
                     WindowEvent::SelectNext => (),
                     WindowEvent::SelectPrev => (),
+                    WindowEvent::Shortcut { .. } => (),
                 }
             }
         }
     }
 
-    fn traverse_elements_mut(&mut self, key: ElementKey, f: &mut dyn FnMut(&mut Element<Msg>)) {
+    /// Calls `f` on the `Element`, its children and overlays
+    pub(crate) fn traverse_elements_mut(
+        &mut self,
+        key: ElementKey,
+        f: &mut dyn FnMut(&mut Element<Msg>),
+    ) {
         let element = match self.elements.get_mut(&key) {
             Some(element) => element,
             None => return,
         };
         f(element);
+        let overlays = element.overlays.clone();
         match element.children.to_owned() {
             Children::Element(child) => {
                 self.traverse_elements_mut(child, f);
@@ -517,58 +889,163 @@ where
                     self.traverse_elements_mut(child.element, f);
                 }
             }
-            Children::None => return,
+            Children::None => (),
+        }
+        for overlay in overlays {
+            self.traverse_elements_mut(overlay, f);
         }
     }
 
     pub fn resize(&mut self, size: (u32, u32), queue: &wgpu::Queue) {
         self.resolve_events();
         self.size = size;
+        self.resized = false;
         self.gpu.resize((size.0, size.1), queue);
-        let entry_key = if let Some(entry) = &self.entry {
-            entry
-        } else {
-            return;
-        };
         /*if let Some(entry) = self.elements.get_mut(&entry_key) {
             entry.styles.flags.recalc_transform = true;
         }*/
-        self.element_transform(
-            *entry_key,
-            &ElementTransform {
-                position: Point::new(size.0 as f32 / 2.0, size.1 as f32 / 2.0),
-                scale: Point::new(size.0 as f32, size.1 as f32),
-                rotation: 0.0,
-            },
-        );
+        self.layout_layers();
     }
 
     pub fn update(&mut self) {
         self.resolve_events();
-        let entry_key = if let Some(entry) = self.entry {
-            entry
-        } else {
-            return;
-        };
+        let roots = self.layers.values().copied().collect::<Vec<_>>();
+        for root in &roots {
+            self.cascade(*root, None, false);
+        }
+        self.style_sheet.dirty = false;
+        self.base_sheet.dirty = false;
+        self.theme_dirty = false;
         self.ordered.clear();
         self.select.selectables.clear();
-        self.order(entry_key);
-        let mut ordered = self.ordered.clone();
-        ordered.sort_by(|a, b| {
-            self.get_element(*a)
-                .map(|e| e.styles.z_index)
-                .unwrap_or(0)
-                .cmp(&self.get_element(*b).map(|e| e.styles.z_index).unwrap_or(0))
-        });
-        self.ordered = ordered;
-        self.element_transform(
-            entry_key,
-            &ElementTransform {
-                position: Point::new(self.size.0 as f32 / 2.0, self.size.1 as f32 / 2.0),
-                scale: Point::new(self.size.0 as f32, self.size.1 as f32),
-                rotation: 0.0,
-            },
-        );
+        self.input_start = 0;
+        let layers = self.layers.clone();
+        self.tooltip_range = (0, 0);
+        for (layer, root) in layers {
+            let start = self.ordered.len();
+            let visible = self.get_element(root).map(|e| e.styles.visible).unwrap_or(false);
+            if layer.is_modal() && visible {
+                // layers beneath a modal do not receive input
+                self.input_start = start;
+                self.select.selectables.clear();
+            }
+            self.order(root);
+            let mut ordered = self.ordered.split_off(start);
+            ordered.sort_by(|a, b| {
+                self.get_element(*a)
+                    .map(|e| e.styles.z_index)
+                    .unwrap_or(0)
+                    .cmp(&self.get_element(*b).map(|e| e.styles.z_index).unwrap_or(0))
+            });
+            self.ordered.append(&mut ordered);
+            if layer == Layer::Tooltip {
+                self.tooltip_range = (start, self.ordered.len());
+            }
+        }
+        self.layout_layers();
+    }
+
+    /// Lays out all layers, see `Gui::layout`
+    fn layout_layers(&mut self) {
+        let modal = self
+            .layers
+            .iter()
+            .rev()
+            .find(|(layer, root)| {
+                layer.is_modal()
+                    && self.get_element(**root).map(|e| e.styles.visible).unwrap_or(false)
+            })
+            .map(|(layer, _)| *layer);
+        let layers = self.layers.clone();
+        for (layer, root) in layers {
+            self.input_blocked = modal.map(|modal| layer < modal).unwrap_or(false);
+            self.layout(root);
+        }
+        self.input_blocked = false;
+        self.place_tooltip();
+    }
+
+    /// Lays out the tree of `key` over the whole window, then its overlays
+    fn layout(&mut self, key: ElementKey) {
+        self.overlay_queue.clear();
+        let window = ElementTransform {
+            position: Point::new(self.size.0 as f32 / 2.0, self.size.1 as f32 / 2.0),
+            scale: Point::new(self.size.0 as f32, self.size.1 as f32),
+            rotation: 0.0,
+        };
+        let container = self.detached_container(key, window);
+        self.element_transform(key, &container);
+        // overlays can queue more overlays
+        let mut i = 0;
+        while i < self.overlay_queue.len() {
+            let (key, parent) = self.overlay_queue[i].clone();
+            i += 1;
+            let container = self.detached_container(key, parent);
+            self.element_transform(key, &container);
+        }
+        self.overlay_queue.clear();
+    }
+
+    /// Container of an overlay or a layer root, see `Parent::Element`
+    fn detached_container(&self, key: ElementKey, parent: ElementTransform) -> ElementTransform {
+        let target = match self.get_element(key).map(|e| e.styles.position.get().parent()) {
+            Some(Parent::Element(target)) => target,
+            _ => return parent,
+        };
+        match self.get_element(target) {
+            Some(target) => target.transform.clone(),
+            None => parent,
+        }
+    }
+
+    /// Resolves classes, inheritance, states and theme references of the `Element`
+    /// and its children
+    ///
+    /// Classes are only merged again when they, the style sheets or the
+    /// `inherited` styles changed
+    fn cascade(&mut self, key: ElementKey, inherited: Option<&Inherited>, inherited_changed: bool) {
+        let element = match self.elements.get_mut(&key) {
+            Some(element) => element,
+            None => return,
+        };
+        let state = ElementState {
+            hovered: self.input.hover == Some(key),
+            focused: self.select.selected == Some(key),
+            pressed: self.input.pressed == Some(key),
+            disabled: element.styles.disabled,
+        };
+        let sheets_dirty = self.style_sheet.dirty || self.base_sheet.dirty;
+        let changed = inherited_changed || sheets_dirty || element.classes_dirty;
+        let restate = changed || state != element.state;
+        // the classes are only needed when the element is restyled
+        if restate {
+            // defaults of all classes first, so the style sheet always overrides them
+            let mut classes = [&self.base_sheet, &self.style_sheet]
+                .into_iter()
+                .flat_map(|sheet| element.classes.iter().filter_map(|class| sheet.get_class(class)))
+                .collect::<Vec<&Styles>>();
+            if let Some(styles) = element
+                .label
+                .as_ref()
+                .and_then(|label| self.style_sheet.get_label(label))
+            {
+                classes.push(styles);
+            }
+            if changed {
+                element.styles.cascade(&classes, inherited);
+                element.classes_dirty = false;
+            }
+            element.styles.restate(&classes, state);
+            element.state = state;
+        }
+        element.styles.retheme(&self.theme, self.theme_dirty);
+        let propagate = element.styles.take_inherited_changed();
+        let inherited = element.styles.inherited();
+        let mut children = element.children.keys();
+        children.extend_from_slice(&element.overlays);
+        for child in children {
+            self.cascade(child, Some(&inherited), propagate);
+        }
     }
 
     fn order(&mut self, key: ElementKey) {
@@ -580,7 +1057,7 @@ where
         } else {
             return;
         };
-        if element.styles.selectable {
+        if element.styles.selectable && !element.styles.disabled {
             self.select.selectables.push(key);
         }
         self.ordered.push(key);
@@ -589,6 +1066,7 @@ where
         } else {
             return;
         };
+        let overlays = element.overlays.clone();
         match &element.children {
             Children::Element(key) => self.order(*key),
             Children::Layers(layers) => {
@@ -617,36 +1095,49 @@ where
             }
             Children::None => (),
         }
+        for key in overlays {
+            self.order(key);
+        }
     }
 
     pub fn prepare(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+        if self.resized {
+            self.gpu.resize(self.size, queue);
+            self.resized = false;
+        }
         let mut font = self.font_system.take().unwrap();
         let mut swash = self.swash_cache.take().unwrap();
         for i in 0..self.ordered.len() {
-            let e = if let Some(e) = self.get_element_mut(self.ordered[i]) {
+            let e = if let Some(e) = self.elements.get_mut(&self.ordered[i]) {
                 e
             } else {
                 continue;
             };
-            e.write(device, queue, &mut font, &mut swash)
+            e.write(device, queue, &mut font, &mut swash, &self.theme)
         }
         self.font_system = Some(font);
         self.swash_cache = Some(swash);
     }
 
     fn element_transform(&mut self, key: ElementKey, transform: &ElementTransform) {
-        let (container, view_port) = (
+        let (mut container, view_port): (Container, _) = (
             transform.clone().into(),
-            ViewPort(self.size.0 as f32, self.size.1 as f32),
+            ViewPort::new(self.size.0 as f32, self.size.1 as f32)
+                .with_scale_factor(self.scale_factor),
         );
         let element = match self.elements.get_mut(&key) {
             Some(element) => element,
             None => return,
         };
-        let element = match self.elements.get_mut(&key) {
-            Some(element) => element,
-            None => return,
-        };
+        // `Value::Image` measures the part of the texture that is drawn
+        container.image = element.styles.texture.get().as_ref().map(|texture| {
+            let (width, height) = texture.size();
+            let uv = element.styles.uv_rect.get();
+            Rectangle {
+                position: container.position,
+                size: Point::new(uv.width * width as f32, uv.height * height as f32),
+            }
+        });
         if true {
             let (width, height) = (
                 element
@@ -656,31 +1147,61 @@ where
                     .styles
                     .height.get().calc(&container, &view_port),
             );
-            let pos = 
-                element
-                    .styles
-                    .position
-                    .get()
-                    .calc(&container, &view_port)
-            ;
+            let image = container.image.map(|image| (image.size.x, image.size.y));
+            let (width, height) = element.styles.aspect_ratio.get().fit(width, height, image);
+            let limit = |value: &Option<Values>, default: f32| {
+                value
+                    .as_ref()
+                    .map(|value| value.calc(&container, &view_port))
+                    .unwrap_or(default)
+            };
+            let width = width
+                .min(limit(element.styles.max_width.get(), f32::INFINITY))
+                .max(limit(element.styles.min_width.get(), 0.0));
+            let height = height
+                .min(limit(element.styles.max_height.get(), f32::INFINITY))
+                .max(limit(element.styles.min_height.get(), 0.0));
+            let pos = element.styles.position.get().calc(
+                &container,
+                &view_port,
+                Point::new(width, height),
+            );
             let rotation = element
                 .styles
                 .rotation
                 .get()
                 .calc(&container, &view_port);
+            let edges_radius = element.styles.edges_radius.get().calc(&container, &view_port);
+            let edges_smooth = element.styles.edges_smooth.get().calc(&container, &view_port);
+            let font_size = element.styles.text_size.get().calc(&container, &view_port);
+
+            let transitions = element.styles.transitions.get().clone();
+            let theme = &self.theme;
+            let resolve = |value: &AnimatedValue| value.channels(&container, &view_port, theme);
+            let animator = &mut element.animator;
+            let width = animator.apply_scalar(Property::Width, width, &transitions, &resolve);
+            let height = animator.apply_scalar(Property::Height, height, &transitions, &resolve);
+            let pos = animator.apply(Property::Position, [pos.x, pos.y, 0.0, 0.0], &transitions, &resolve);
+            let offset = animator.sample(Property::Offset, &resolve).unwrap_or_default();
+            let rotation = animator.apply_scalar(Property::Rotation, rotation, &transitions, &resolve);
+            let edges_radius =
+                animator.apply_scalar(Property::EdgesRadius, edges_radius, &transitions, &resolve);
+            let edges_smooth =
+                animator.apply_scalar(Property::EdgesSmooth, edges_smooth, &transitions, &resolve);
+            let font_size = animator.apply_scalar(Property::TextSize, font_size, &transitions, &resolve);
+
             let transform = ElementTransform {
-                position: pos,
+                position: Point::new(pos[0] + offset[0], pos[1] + offset[1]),
                 scale: Point::new(width, height),
                 rotation,
             };
-            let edges_radius = element.styles.edges_radius.get().calc(&container, &view_port);
+            let round = element.styles.round.get().unwrap_or(self.round);
+            let transform = round.apply(transform, self.scale_factor);
             element.render_element.1.edges[0] = edges_radius;
-            let edges_smooth = element.styles.edges_smooth.get().calc(&container, &view_port);
             element.render_element.1.edges[1] = edges_smooth;
-
-            let font_size = element.styles.text_size.get().calc(&container, &view_port);
             element.render_element.1.text_size = font_size;
             let pre_collision = element.transform.point_collision(self.input.mouse);
+            let pre_size = element.transform.pixel_size();
 
             element.transform = transform;
             /*element.styles.flags.dirty_transform = true;
@@ -688,7 +1209,8 @@ where
             element.styles.flags.recalc_transform = false;*/
 
             let post_collision = element.transform.point_collision(self.input.mouse);
-            match (pre_collision, post_collision) {
+            let disabled = element.styles.disabled || self.input_blocked;
+            match (pre_collision && !disabled, post_collision && !disabled) {
                 (true, false) => {
                     if let Some(listeners) = element.events.get(&EventTypes::MouseLeave) {
                         for EventListener { msg, .. } in listeners {
@@ -703,7 +1225,7 @@ where
                                     &element,
                                     &self.input,
                                 ),
-                                window_event: event,
+                                window_event: Some(event),
                                 msg: msg.clone(),
                                 key,
                             });
@@ -724,7 +1246,7 @@ where
                                     &element,
                                     &self.input,
                                 ),
-                                window_event: event,
+                                window_event: Some(event),
                                 msg: msg.clone(),
                                 key,
                             });
@@ -733,8 +1255,26 @@ where
                 }
                 _ => {}
             }
+            let (width, height) = element.transform.pixel_size();
+            if (width, height) != pre_size {
+                if let Some(listeners) = element.events.get(&EventTypes::Resize) {
+                    for EventListener { msg, .. } in listeners {
+                        self.events.events.push(events::Event {
+                            event_type: EventTypes::Resize,
+                            // layout is not caused by any window event
+                            window_event: None,
+                            element_event: ElementEvent::Resize { width, height },
+                            msg: msg.clone(),
+                            key,
+                        });
+                    }
+                }
+            }
         }
         let transform = &element.transform;
+        for overlay in &element.overlays {
+            self.overlay_queue.push((*overlay, transform.clone()));
+        }
         match element.children.to_owned() {
             Children::Element(child) => {
                 let padding = element.styles.padding.get().calc(&container, &view_port);
@@ -897,6 +1437,11 @@ impl ElementTransform {
             && point_rotated.y <= y_max
     }
 
+    /// Width and height rounded to whole pixels
+    pub fn pixel_size(&self) -> (u32, u32) {
+        (self.scale.x.round().max(0.0) as u32, self.scale.y.round().max(0.0) as u32)
+    }
+
     pub fn calc_side(&self, (size, side): &(Values, Side), view_port: &(u32, u32)) -> f32 {
         todo!()
     }
@@ -914,7 +1459,22 @@ where
     pub styles: styles::styles_proposition::Styles,
     pub events: EventListeners<Msg>,
     pub children: Children,
+    /// Child `Elements` positioned outside of the layout of `children`
+    ///
+    /// Overlays are laid out after the whole tree using their `Position`, so they can
+    /// be placed relative to the parent, the view port or another `Element`
+    pub overlays: Vec<ElementKey>,
+    pub tooltip: Option<Tooltip>,
+    widget: Option<Widget<Msg>>,
+    classes: Vec<String>,
+    classes_dirty: bool,
+    state: ElementState,
+    animator: Animator,
+    text_color: [f32; 4],
     text_buffer: Option<cosmic_text::Buffer>,
+    /// Last placement of the texture written to the gpu
+    texture_params: TextureParams,
+    playback: Option<Playback>,
     transform: ElementTransform,
     _parent: ElementTransform,
 }
@@ -1022,7 +1582,17 @@ where
             styles: styles::styles_proposition::Styles::default(),
             events: EventListeners::new(),
             children: Children::None,
+            overlays: Vec::new(),
+            tooltip: None,
+            widget: None,
+            classes: Vec::new(),
+            classes_dirty: true,
+            state: ElementState::default(),
+            animator: Animator::default(),
+            text_color: [0.0; 4],
             text_buffer: None,
+            texture_params: TextureParams::default(),
+            playback: None,
             transform: ElementTransform::zeroed(),
             _parent: ElementTransform::zeroed(),
         }
@@ -1034,6 +1604,31 @@ where
         self
     }
 
+    /// Plays an animated texture as the background, see `Element::set_animated_texture`
+    pub fn with_animated_texture(mut self, texture: Arc<AnimatedTexture>) -> Self {
+        self.set_animated_texture(Some(texture));
+        self
+    }
+
+    /// Plays an animated texture as the background in a loop
+    ///
+    /// Frames advance with `Gui::advance` and replace the texture style.
+    /// Control the playback using `Element::playback_mut`.
+    pub fn set_animated_texture(&mut self, texture: Option<Arc<AnimatedTexture>>) {
+        self.playback = texture.map(Playback::new);
+        if let Some(frame) = self.playback.as_mut().and_then(|playback| playback.advance(0.0)) {
+            self.styles.set_region(&frame);
+        }
+    }
+
+    pub fn playback(&self) -> Option<&Playback> {
+        self.playback.as_ref()
+    }
+
+    pub fn playback_mut(&mut self) -> Option<&mut Playback> {
+        self.playback.as_mut()
+    }
+
     /// Configures styles for `Element`
     pub fn with_styles(mut self, styles: styles::styles_proposition::Styles) -> Self {
         self.styles = styles;
@@ -1046,6 +1641,73 @@ where
         self
     }
 
+    /// Adds an overlay child, see `Element::overlays`
+    pub fn with_overlay(mut self, overlay: ElementKey) -> Self {
+        self.overlays.push(overlay);
+        self
+    }
+
+    /// Shows a tooltip after the `Element` is hovered for a while
+    pub fn with_tooltip(mut self, tooltip: Tooltip) -> Self {
+        self.tooltip = Some(tooltip);
+        self
+    }
+
+    /// Adds a style class to `Element`
+    pub fn with_class(mut self, class: &str) -> Self {
+        self.add_class(class);
+        self
+    }
+
+    /// Adds a style class to `Element`
+    ///
+    /// Classes added later take precedence over earlier ones
+    pub fn add_class(&mut self, class: &str) {
+        if self.classes.iter().any(|c| c == class) {
+            return;
+        }
+        self.classes.push(class.to_string());
+        self.classes_dirty = true;
+    }
+
+    /// Removes a style class from `Element`
+    pub fn remove_class(&mut self, class: &str) {
+        self.classes.retain(|c| c != class);
+        self.classes_dirty = true;
+    }
+
+    /// Returns style classes of `Element`
+    pub fn classes(&self) -> &[String] {
+        &self.classes
+    }
+
+    /// Starts a keyframe animation
+    ///
+    /// Animations of the same property started later take precedence
+    pub fn animate(&mut self, animation: Animation) {
+        self.animator.play(animation);
+    }
+
+    /// Stops all animations with the given name
+    pub fn stop_animation(&mut self, name: &str) {
+        self.animator.stop(Some(name));
+    }
+
+    /// Stops all animations
+    pub fn stop_animations(&mut self) {
+        self.animator.stop(None);
+    }
+
+    /// Returns true if any transition or animation is running
+    pub fn is_animating(&self) -> bool {
+        self.animator.is_animating()
+    }
+
+    /// Returns the state used to resolve state styles during the last update
+    pub fn state(&self) -> ElementState {
+        self.state
+    }
+
     pub fn event_listen(mut self, event_type: EventTypes, msg: Msg) -> Self {
         self.events.listen(event_type, msg);
         self
@@ -1061,18 +1723,78 @@ where
         self
     }
 
+    /// Places the background texture using the fit, alignment, tiling and nine-slice styles
+    fn texture_params(&self, texture: &texture::Texture) -> TextureParams {
+        let (width, height) = texture.size();
+        let uv = *self.styles.uv_rect.get();
+        let natural = (uv.width * width as f32, uv.height * height as f32);
+        let size = (self.transform.scale.x, self.transform.scale.y);
+        let (w, h) = self.styles.fit.get().size(natural, size);
+        let align = self.styles.image_align.get();
+        let (slice_uv, slice_px) = match self.styles.nine_slice.get() {
+            Some(slice) => slice.borders(natural.0, natural.1),
+            None => Default::default(),
+        };
+        let tiling = match self.styles.tiling.get() {
+            Tiling::None => 0.0,
+            Tiling::Repeat => 1.0,
+            Tiling::Mirror => 2.0,
+        };
+        TextureParams {
+            slice_uv,
+            slice_px,
+            uv_rect: [uv.x, uv.y, uv.width, uv.height],
+            image_rect: [(size.0 - w) * align.x, (size.1 - h) * align.y, w, h],
+            tiling: [tiling, 0.0, 0.0, 0.0],
+        }
+    }
+
     pub(crate) fn write(
         &mut self,
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         font_system: &mut FontSystem,
         swash_cache: &mut SwashCache,
+        theme: &Theme,
     ) {
         if let None = &self.render_element.0 {
             self.render_element.0 = Some(RenderElement::zeroed(device))
         }
         let mut render_element = self.render_element.0.take().unwrap();
-        self.render_element.1.color = self.styles.bg_color.get().to_rgba().into();
+        let transitions = self.styles.transitions.get().clone();
+        // colors and numbers do not depend on the view port
+        let container = self.transform.clone().into();
+        let view_port = ViewPort::new(0.0, 0.0);
+        let resolve = |value: &AnimatedValue| value.channels(&container, &view_port, theme);
+        let bg_color = self.animator.apply(
+            Property::BgColor,
+            self.styles.bg_color.get().to_rgba().into(),
+            &transitions,
+            &resolve,
+        );
+        let text_color = self.animator.apply(
+            Property::TextColor,
+            self.styles.text_color.get().to_rgba().into(),
+            &transitions,
+            &resolve,
+        );
+        let alpha = self.animator.apply_scalar(
+            Property::Alpha,
+            *self.styles.alpha.get(),
+            &transitions,
+            &resolve,
+        );
+        if bg_color != <[f32; 4]>::from(self.render_element.1.color) {
+            self.styles.bg_color.dirty = true;
+        }
+        if text_color != self.text_color {
+            self.text_color = text_color;
+            self.styles.text_color.dirty = true;
+        }
+        if alpha != self.render_element.1.alpha {
+            self.styles.alpha.dirty = true;
+        }
+        self.render_element.1.color = bg_color.into();
         if self.styles.texture.dirty {
             if let Some(texture) = &self.styles.texture.get() {
                 render_element.set_texture(texture.clone());
@@ -1080,8 +1802,16 @@ where
 
             self.styles.texture.dirty = false;
         }
+        if let Some(texture) = self.styles.texture.get() {
+            // placement depends on the size of the element, so it is compared every frame
+            let params = self.texture_params(texture);
+            if params != self.texture_params {
+                render_element.set_texture_params(params, queue);
+                self.texture_params = params;
+            }
+        }
         if self.styles.bg_color.dirty {
-            let color = self.styles.bg_color.get().to_rgba().into();
+            let color = bg_color.into();
             render_element.set_color(color, queue, device);
             self.styles.bg_color.dirty = false;
         }
@@ -1109,10 +1839,17 @@ where
 
         }
         if self.styles.alpha.dirty {
-            let alpha = *self.styles.alpha.get();
             self.render_element.1.alpha = alpha;
             self.styles.alpha.dirty = false;
         }
+        if self.styles.text_color.dirty || self.styles.text_size.dirty || self.styles.font.dirty {
+            if let Some((_, flag)) = &mut self.text {
+                *flag = true;
+            }
+            self.styles.text_color.dirty = false;
+            self.styles.text_size.dirty = false;
+            self.styles.font.dirty = false;
+        }
         //if self.styles.flags.dirty_transform {
             let transform = &self.transform;
             self.render_element.1.update_transform(transform);
@@ -1166,15 +1903,18 @@ where
                                 self.render_element.1.text_size+3.0,
                             ));
                             tb.set_size(Some(self.transform.scale.x), Some(self.transform.scale.y));
-                            let attrs = Attrs::new();
+                            let attrs = match self.styles.font.get() {
+                                Some(font) => Attrs::new().family(Family::Name(font)),
+                                None => Attrs::new(),
+                            };
                             tb.set_text(&txt, attrs, cosmic_text::Shaping::Advanced);
-                            let color = self.styles.text_color.get().to_rgba();
+                            let color = self.text_color;
                             let mut image = DynamicImage::new(
                                 self.transform.scale.x as u32,
                                 self.transform.scale.y as u32,
                                 image::ColorType::Rgba8,
                             );
-                            tb.draw(swash_cache, cosmic_text::Color::rgba((color.0 * 255.0) as u8, (color.1 * 255.0) as u8, (color.2 * 255.0) as u8, (color.3 * 255.0) as u8), |x, y, _, _, color| {
+                            tb.draw(swash_cache, cosmic_text::Color::rgba((color[0] * 255.0) as u8, (color[1] * 255.0) as u8, (color[2] * 255.0) as u8, (color[3] * 255.0) as u8), |x, y, _, _, color| {
                                 if x < 0
                                     || y < 0
                                     || x >= self.transform.scale.x as i32
@@ -1185,8 +1925,9 @@ where
                                 image.put_pixel(x as u32, y as u32, color.as_rgba().into())
                             });
                             self.text_buffer = Some(tb.clone());
+                            // elements without any area have no text
                             let tex = texture::Texture::from_image(device, queue, &image, None);
-                            render_element.text = Some(Arc::new(tex))
+                            render_element.text = tex.ok().map(Arc::new)
                         }
                         None => {
                             let mut tb = cosmic_text::Buffer::new(
@@ -1198,16 +1939,19 @@ where
                             );
                             let mut tb = tb.borrow_with(font_system);
                             tb.set_size(Some(self.transform.scale.x), Some(self.transform.scale.y));
-                            let attrs = Attrs::new();
+                            let attrs = match self.styles.font.get() {
+                                Some(font) => Attrs::new().family(Family::Name(font)),
+                                None => Attrs::new(),
+                            };
                             tb.set_text(&txt, attrs, cosmic_text::Shaping::Advanced);
                             tb.shape_until_scroll(true);
-                            let color = self.styles.text_color.get().to_rgba();
+                            let color = self.text_color;
                             let mut image = DynamicImage::new(
                                 self.transform.scale.x as u32,
                                 self.transform.scale.y as u32,
                                 image::ColorType::Rgba8,
                             );
-                            tb.draw(swash_cache, cosmic_text::Color::rgba((color.0 * 255.0) as u8, (color.1 * 255.0) as u8, (color.2 * 255.0) as u8, (color.3 * 255.0) as u8), |x, y, _, _, color| {
+                            tb.draw(swash_cache, cosmic_text::Color::rgba((color[0] * 255.0) as u8, (color[1] * 255.0) as u8, (color[2] * 255.0) as u8, (color[3] * 255.0) as u8), |x, y, _, _, color| {
                                 if x < 0
                                     || y < 0
                                     || x >= self.transform.scale.x as i32
@@ -1218,8 +1962,9 @@ where
                                 image.put_pixel(x as u32, y as u32, color.as_rgba().into())
                             });
                             self.text_buffer = Some(tb.clone());
+                            // elements without any area have no text
                             let tex = texture::Texture::from_image(device, queue, &image, None);
-                            render_element.text = Some(Arc::new(tex))
+                            render_element.text = tex.ok().map(Arc::new)
                         }
                     }
                     *dirty = false;
@@ -1282,6 +2027,30 @@ where
     }
 }
 
+/// Root layers of the `Gui`, each with its own tree of `Elements`
+///
+/// Layers are rendered in the order of declaration regardless of `z_index`,
+/// `z_index` only orders `Elements` within their layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Layer {
+    /// The main user interface, see `Gui::set_entry`
+    Base,
+    /// Blocks input to the layers beneath while its root is visible
+    Modal,
+    /// Used by the list of an open `widgets::Dropdown` and open menus, see `menu`
+    ///
+    /// Above `Modal`, so pickers inside of modal dialogs are shown and usable
+    Popup,
+    Tooltip,
+    Debug,
+}
+
+impl Layer {
+    pub fn is_modal(&self) -> bool {
+        matches!(self, Layer::Modal)
+    }
+}
+
 /// Describes how many `Children` an `Element` has and how they should be positioned
 #[derive(Clone, Debug, Default)]
 pub enum Children {
@@ -1305,6 +2074,20 @@ pub enum Children {
     None,
 }
 
+impl Children {
+    /// Returns keys of all child `Elements`
+    pub fn keys(&self) -> Vec<ElementKey> {
+        match self {
+            Children::Element(key) => vec![*key],
+            Children::Layers(keys) => keys.clone(),
+            Children::Rows { children, .. } | Children::Columns { children, .. } => {
+                children.iter().map(|section| section.element).collect()
+            }
+            Children::None => Vec::new(),
+        }
+    }
+}
+
 /// Describes allocated space for a child `Element` inside rows/columns
 #[derive(Clone, Debug)]
 pub struct Section {
@@ -1340,13 +2123,12 @@ impl Point {
     }
 }
 
+/// Decodes an image, see `texture::Texture::from_bytes`
 pub fn load_texture_from_memory(
     device: &wgpu::Device,
     queue: &wgpu::Queue,
     data: &[u8],
-) -> texture::Texture {
-    let img = image::load_from_memory(data).unwrap();
-    let img = img.to_rgba8();
-    let img = DynamicImage::ImageRgba8(img);
+) -> Result<texture::Texture> {
+    let img = image::load_from_memory(data)?;
     texture::Texture::from_image(device, queue, &img, None)
-}
\ No newline at end of file
+}
@@ -0,0 +1,679 @@
+//! Menu bars, context menus and keyboard shortcuts
+//!
+//! Open menus are overlays of the root of `Layer::Popup`, which is above `Layer::Modal`
+//! so context menus of modal dialogs work. Clicking an item emits
+//! its `Msg` with `EventTypes::Click`, checkable items emit it with `EventTypes::Change`.
+//!
+//! ```ignore
+//! let file = Menu::new()
+//!     .with_item(MenuItem::action("Save", Msg::Save).with_shortcut(Shortcut::ctrl('s')))
+//!     .with_item(MenuItem::separator())
+//!     .with_item(MenuItem::check("Autosave", true, Msg::Autosave));
+//! let bar = MenuBar::new().with_menu("File", file).build(&mut gui);
+//!
+//! // on right click
+//! gui.open_context_menu(Menu::new().with_item(MenuItem::action("Delete", Msg::Delete)));
+//! ```
+//!
+//! Menus are styled with the classes `menu-bar`, `menu-bar-item`, `menu`, `menu-item`,
+//! `menu-separator`, `menu-check`, `menu-label`, `menu-shortcut` and `menu-arrow`.
+//! Checked items and the title of the open menu of a menu bar get the class `checked`.
+
+use std::fmt;
+
+use crate::{
+    events::{ElementEvent, Event, EventTypes, WindowEvent},
+    styles::styles_proposition::{Colors, Position, PositionValues, Side, Styles, Values},
+    widgets::WidgetValue,
+    Children, Element, ElementKey, Gui, Layer, Section,
+};
+
+/// Height of menu items in logical pixels
+const ITEM_HEIGHT: f32 = 26.0;
+/// Height of separators in logical pixels
+const SEPARATOR_HEIGHT: f32 = 9.0;
+const MENU_WIDTH: f32 = 220.0;
+
+/// Key combination that activates a menu item
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Shortcut {
+    /// Control has to be held, see `WindowEvent::Shortcut`
+    pub control: bool,
+    pub key: char,
+}
+
+impl Shortcut {
+    /// Key pressed while holding control
+    pub fn ctrl(key: char) -> Self {
+        Self {
+            control: true,
+            key: key.to_ascii_lowercase(),
+        }
+    }
+
+    /// Key pressed while no `Element` is selected
+    pub fn key(key: char) -> Self {
+        Self {
+            control: false,
+            key: key.to_ascii_lowercase(),
+        }
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.control {
+            write!(f, "Ctrl+")?;
+        }
+        write!(f, "{}", self.key.to_ascii_uppercase())
+    }
+}
+
+/// Entry of a `Menu`
+#[derive(Debug, Clone)]
+pub enum MenuItem<Msg> {
+    Action {
+        text: String,
+        shortcut: Option<Shortcut>,
+        msg: Msg,
+    },
+    /// Item that is toggled when clicked
+    Check {
+        text: String,
+        shortcut: Option<Shortcut>,
+        checked: bool,
+        msg: Msg,
+    },
+    /// Opens a nested menu when hovered
+    Submenu { text: String, menu: Menu<Msg> },
+    Separator,
+}
+
+impl<Msg> MenuItem<Msg> {
+    pub fn action(text: &str, msg: Msg) -> Self {
+        Self::Action {
+            text: text.to_string(),
+            shortcut: None,
+            msg,
+        }
+    }
+
+    pub fn check(text: &str, checked: bool, msg: Msg) -> Self {
+        Self::Check {
+            text: text.to_string(),
+            shortcut: None,
+            checked,
+            msg,
+        }
+    }
+
+    pub fn submenu(text: &str, menu: Menu<Msg>) -> Self {
+        Self::Submenu {
+            text: text.to_string(),
+            menu,
+        }
+    }
+
+    pub fn separator() -> Self {
+        Self::Separator
+    }
+
+    /// Shows and handles a shortcut, only applies to actions and checkable items
+    pub fn with_shortcut(mut self, new: Shortcut) -> Self {
+        if let Self::Action { shortcut, .. } | Self::Check { shortcut, .. } = &mut self {
+            *shortcut = Some(new);
+        }
+        self
+    }
+
+    fn shortcut(&self) -> Option<Shortcut> {
+        match self {
+            Self::Action { shortcut, .. } | Self::Check { shortcut, .. } => *shortcut,
+            _ => None,
+        }
+    }
+}
+
+/// List of `MenuItems`
+#[derive(Debug, Clone)]
+pub struct Menu<Msg> {
+    pub items: Vec<MenuItem<Msg>>,
+}
+
+impl<Msg> Menu<Msg> {
+    pub fn new() -> Self {
+        Self { items: Vec::new() }
+    }
+
+    pub fn with_item(mut self, item: MenuItem<Msg>) -> Self {
+        self.items.push(item);
+        self
+    }
+
+    /// Finds the path of the item with `shortcut` in this menu and its submenus
+    fn find_shortcut(&self, shortcut: Shortcut, path: &mut Vec<usize>) -> bool {
+        for (i, item) in self.items.iter().enumerate() {
+            path.push(i);
+            let found = match item {
+                MenuItem::Submenu { menu, .. } => menu.find_shortcut(shortcut, path),
+                item => item.shortcut() == Some(shortcut),
+            };
+            if found {
+                return true;
+            }
+            path.pop();
+        }
+        false
+    }
+}
+
+impl<Msg> Default for Menu<Msg> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Row of menu titles, usually placed at the top of the root
+pub struct MenuBar<Msg> {
+    menus: Vec<(String, Menu<Msg>)>,
+}
+
+impl<Msg: Clone> MenuBar<Msg> {
+    pub fn new() -> Self {
+        Self { menus: Vec::new() }
+    }
+
+    pub fn with_menu(mut self, title: &str, menu: Menu<Msg>) -> Self {
+        self.menus.push((title.to_string(), menu));
+        self
+    }
+
+    /// Shortcuts of the menus are handled while the menu bar exists
+    pub fn build(self, gui: &mut Gui<Msg>) -> ElementKey {
+        gui.default_menu_classes();
+        let mut titles = Vec::new();
+        let mut menus = Vec::new();
+        for (title, menu) in self.menus {
+            let mut element = Element::new().with_class("menu-bar-item");
+            element.set_text(Some(title));
+            titles.push(gui.add_element(element));
+            menus.push(menu);
+        }
+        let key = gui.add_element(
+            Element::new()
+                .with_class("menu-bar")
+                .with_children(Children::Columns {
+                    children: titles
+                        .iter()
+                        .map(|title| Section {
+                            element: *title,
+                            size: Some(Values::logical(80.0)),
+                        })
+                        .collect(),
+                    spacing: None,
+                }),
+        );
+        gui.menus.bars.push(MenuBarState { key, titles, menus });
+        key
+    }
+}
+
+impl<Msg: Clone> Default for MenuBar<Msg> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub(crate) struct MenuState<Msg> {
+    bars: Vec<MenuBarState<Msg>>,
+    context: Option<Menu<Msg>>,
+    /// Root of `Layer::Popup` while menus are open
+    layer: Option<ElementKey>,
+    /// Open menu and its open submenus
+    open: Vec<OpenMenu>,
+    /// Title of the menu bar whose menu is open
+    title: Option<ElementKey>,
+}
+
+impl<Msg> Default for MenuState<Msg> {
+    fn default() -> Self {
+        Self {
+            bars: Vec::new(),
+            context: None,
+            layer: None,
+            open: Vec::new(),
+            title: None,
+        }
+    }
+}
+
+struct MenuBarState<Msg> {
+    key: ElementKey,
+    titles: Vec<ElementKey>,
+    menus: Vec<Menu<Msg>>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum MenuSource {
+    /// Menu of a menu bar
+    Bar(ElementKey, usize),
+    Context,
+}
+
+struct OpenMenu {
+    source: MenuSource,
+    /// Indices of submenu items leading to the menu
+    path: Vec<usize>,
+    element: ElementKey,
+    /// `Element` of each item
+    items: Vec<ElementKey>,
+    /// All `Elements` created for the menu
+    elements: Vec<ElementKey>,
+}
+
+impl<Msg: Clone> Gui<Msg> {
+    /// Opens a menu at the cursor, closing other open menus
+    pub fn open_context_menu(&mut self, menu: Menu<Msg>) {
+        self.close_menus();
+        self.menus.context = Some(menu);
+        let mouse = self.input.mouse;
+        let position = Position::top_left()
+            .in_view_port()
+            .with_offset(mouse.x, mouse.y)
+            .with_pivot(PositionValues::TopLeft);
+        self.open_menu(MenuSource::Context, Vec::new(), position);
+    }
+
+    /// Closes all open menus
+    pub fn close_menus(&mut self) {
+        self.truncate_menus(0);
+        if let Some(title) = self.menus.title.take().and_then(|t| self.get_element_mut(t)) {
+            title.remove_class("checked");
+        }
+        self.menus.context = None;
+        if let Some(layer) = self.menus.layer {
+            if self.layer(Layer::Popup) == Some(layer) {
+                self.set_layer(Layer::Popup, None);
+            }
+        }
+    }
+
+    /// Handles input of menus, returns true if the event is consumed
+    pub(crate) fn menu_event(&mut self, event: &WindowEvent) -> bool {
+        match event {
+            WindowEvent::MouseDown { .. } => {
+                if let Some((level, index)) = self.menu_item_under_cursor() {
+                    self.activate_menu_item(level, index, event);
+                    return true;
+                }
+                if let Some((bar, menu, title)) = self.menu_title_under_cursor() {
+                    match self.menus.title == Some(title) {
+                        true => self.close_menus(),
+                        false => self.open_bar_menu(bar, menu, title),
+                    }
+                    return true;
+                }
+                if self.menus.open.is_empty() {
+                    return false;
+                }
+                self.close_menus();
+                true
+            }
+            WindowEvent::MouseMove { .. } => {
+                if self.menus.open.is_empty() {
+                    return false;
+                }
+                if let Some((level, index)) = self.menu_item_under_cursor() {
+                    self.hover_menu_item(level, index);
+                } else if let Some((bar, menu, title)) = self.menu_title_under_cursor() {
+                    // switch between menus of the menu bar by hovering
+                    if self.menus.title.is_some() && self.menus.title != Some(title) {
+                        self.open_bar_menu(bar, menu, title);
+                    }
+                }
+                false
+            }
+            WindowEvent::Shortcut { key } => self.trigger_shortcut(Shortcut::ctrl(*key), event),
+            WindowEvent::Input { text } if text == "\u{1b}" && !self.menus.open.is_empty() => {
+                self.close_menus();
+                true
+            }
+            WindowEvent::Input { text } if self.select.selected.is_none() => {
+                let mut chars = text.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(key), None) => self.trigger_shortcut(Shortcut::key(key), event),
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+
+    fn menu_item_under_cursor(&self) -> Option<(usize, usize)> {
+        let mouse = self.input.mouse;
+        self.menus.open.iter().enumerate().rev().find_map(|(level, open)| {
+            let index = open.items.iter().position(|item| {
+                self.get_element(*item)
+                    .map(|e| e.transform.point_collision(mouse))
+                    .unwrap_or(false)
+            })?;
+            Some((level, index))
+        })
+    }
+
+    /// Returns the menu bar, the index of the menu and its title under the cursor
+    fn menu_title_under_cursor(&self) -> Option<(ElementKey, usize, ElementKey)> {
+        let mouse = self.input.mouse;
+        self.menus.bars.iter().find_map(|bar| {
+            let visible = self.bar_receives_input(bar.key);
+            let index = bar.titles.iter().position(|title| {
+                self.get_element(*title)
+                    .map(|e| visible && e.transform.point_collision(mouse))
+                    .unwrap_or(false)
+            })?;
+            Some((bar.key, index, bar.titles[index]))
+        })
+    }
+
+    /// Menu bars that are hidden or beneath a modal ignore clicks and shortcuts
+    fn bar_receives_input(&self, bar: ElementKey) -> bool {
+        self.ordered[self.input_start.min(self.ordered.len())..].contains(&bar)
+    }
+
+    fn open_bar_menu(&mut self, bar: ElementKey, menu: usize, title: ElementKey) {
+        self.close_menus();
+        if let Some(element) = self.get_element_mut(title) {
+            element.add_class("checked");
+        }
+        self.menus.title = Some(title);
+        let position = Position::bottom_left()
+            .relative_to(title)
+            .with_pivot(PositionValues::TopLeft);
+        self.open_menu(MenuSource::Bar(bar, menu), Vec::new(), position);
+    }
+
+    /// Opens the submenu of a hovered item and closes submenus of other items
+    fn hover_menu_item(&mut self, level: usize, index: usize) {
+        if let Some(open) = self.menus.open.get(level + 1) {
+            if open.path.last() == Some(&index) {
+                return;
+            }
+        }
+        self.truncate_menus(level + 1);
+        let open = &self.menus.open[level];
+        let (source, item) = (open.source, open.items[index]);
+        let mut path = open.path.clone();
+        path.push(index);
+        if let Some(MenuItem::Submenu { .. }) = self.menu_item(source, &path) {
+            let position = Position::top_right()
+                .relative_to(item)
+                .with_pivot(PositionValues::TopLeft);
+            self.open_menu(source, path, position);
+        }
+    }
+
+    fn activate_menu_item(&mut self, level: usize, index: usize, event: &WindowEvent) {
+        let open = &self.menus.open[level];
+        let (source, key) = (open.source, open.items[index]);
+        let mut path = open.path.clone();
+        path.push(index);
+        match self.menu_item(source, &path) {
+            Some(MenuItem::Submenu { .. }) => self.hover_menu_item(level, index),
+            Some(MenuItem::Separator) | None => (),
+            Some(_) => {
+                self.emit_menu_item(source, &path, key, event);
+                self.close_menus();
+            }
+        }
+    }
+
+    /// Activates the item with `shortcut` in a menu bar, returns false if there is none
+    fn trigger_shortcut(&mut self, shortcut: Shortcut, event: &WindowEvent) -> bool {
+        let mut path = Vec::new();
+        let found = self
+            .menus
+            .bars
+            .iter()
+            .filter(|bar| self.bar_receives_input(bar.key))
+            .find_map(|bar| {
+                bar.menus.iter().enumerate().find_map(|(i, menu)| {
+                    menu.find_shortcut(shortcut, &mut path)
+                        .then_some(MenuSource::Bar(bar.key, i))
+                })
+            });
+        let source = match found {
+            Some(source) => source,
+            None => return false,
+        };
+        let key = match source {
+            MenuSource::Bar(bar, _) => bar,
+            MenuSource::Context => return false,
+        };
+        self.emit_menu_item(source, &path, key, event);
+        true
+    }
+
+    /// Pushes the event of an item, checkable items are toggled
+    fn emit_menu_item(
+        &mut self,
+        source: MenuSource,
+        path: &[usize],
+        key: ElementKey,
+        event: &WindowEvent,
+    ) {
+        let (event_type, element_event, msg) = match self.menu_item_mut(source, path) {
+            Some(MenuItem::Action { msg, .. }) => (EventTypes::Click, ElementEvent::Click, msg.clone()),
+            Some(MenuItem::Check { checked, msg, .. }) => {
+                *checked = !*checked;
+                let value = WidgetValue::Bool(*checked);
+                (EventTypes::Change, ElementEvent::Change { value }, msg.clone())
+            }
+            _ => return,
+        };
+        self.events.events.push(Event {
+            event_type,
+            window_event: Some(event.clone()),
+            element_event,
+            msg,
+            key,
+        });
+    }
+
+    fn menu(&self, source: MenuSource, path: &[usize]) -> Option<&Menu<Msg>> {
+        let mut menu = match source {
+            MenuSource::Bar(bar, i) => self.menus.bars.iter().find(|b| b.key == bar)?.menus.get(i)?,
+            MenuSource::Context => self.menus.context.as_ref()?,
+        };
+        for i in path {
+            menu = match menu.items.get(*i)? {
+                MenuItem::Submenu { menu, .. } => menu,
+                _ => return None,
+            };
+        }
+        Some(menu)
+    }
+
+    fn menu_item(&self, source: MenuSource, path: &[usize]) -> Option<&MenuItem<Msg>> {
+        let (last, path) = path.split_last()?;
+        self.menu(source, path)?.items.get(*last)
+    }
+
+    fn menu_item_mut(&mut self, source: MenuSource, path: &[usize]) -> Option<&mut MenuItem<Msg>> {
+        let (last, path) = path.split_last()?;
+        let mut menu = match source {
+            MenuSource::Bar(bar, i) => self
+                .menus
+                .bars
+                .iter_mut()
+                .find(|b| b.key == bar)?
+                .menus
+                .get_mut(i)?,
+            MenuSource::Context => self.menus.context.as_mut()?,
+        };
+        for i in path {
+            menu = match menu.items.get_mut(*i)? {
+                MenuItem::Submenu { menu, .. } => menu,
+                _ => return None,
+            };
+        }
+        menu.items.get_mut(*last)
+    }
+
+    /// Creates the `Elements` of a menu and shows it in `Layer::Popup`
+    fn open_menu(&mut self, source: MenuSource, path: Vec<usize>, position: Position) {
+        let menu = match self.menu(source, &path) {
+            Some(menu) => menu.clone(),
+            None => return,
+        };
+        self.default_menu_classes();
+        self.close_dropdown();
+        let mut elements = Vec::new();
+        let mut items = Vec::new();
+        let mut sections = Vec::new();
+        let mut height = 0.0;
+        for item in &menu.items {
+            let (key, size) = match item {
+                MenuItem::Separator => (
+                    self.add_element(Element::new().with_class("menu-separator")),
+                    SEPARATOR_HEIGHT,
+                ),
+                item => (self.menu_item_element(item, &mut elements), ITEM_HEIGHT),
+            };
+            height += size;
+            elements.push(key);
+            items.push(key);
+            sections.push(Section {
+                element: key,
+                size: Some(Values::logical(size)),
+            });
+        }
+        let mut element = Element::new()
+            .with_class("menu")
+            .with_children(Children::Rows {
+                children: sections,
+                spacing: None,
+            });
+        element.styles.position.set(position);
+        element.styles.width.set(Values::logical(MENU_WIDTH));
+        element.styles.height.set(Values::logical(height));
+        let element = self.add_element(element);
+        elements.push(element);
+
+        let layer = match self.menus.layer {
+            Some(layer) => layer,
+            None => {
+                let layer = self.add_element(Element::new());
+                self.menus.layer = Some(layer);
+                layer
+            }
+        };
+        if let Some(layer) = self.get_element_mut(layer) {
+            layer.overlays.push(element);
+        }
+        self.menus.open.push(OpenMenu {
+            source,
+            path,
+            element,
+            items,
+            elements,
+        });
+        self.set_layer(Layer::Popup, Some(layer));
+    }
+
+    /// Creates an item with a check mark, its text, the shortcut and an arrow for submenus
+    fn menu_item_element(&mut self, item: &MenuItem<Msg>, elements: &mut Vec<ElementKey>) -> ElementKey {
+        let (text, shortcut, checked, submenu) = match item {
+            MenuItem::Action { text, shortcut, .. } => (text, *shortcut, None, false),
+            MenuItem::Check {
+                text,
+                shortcut,
+                checked,
+                ..
+            } => (text, *shortcut, Some(*checked), false),
+            MenuItem::Submenu { text, .. } => (text, None, None, true),
+            MenuItem::Separator => unreachable!("separators have no content"),
+        };
+        let mut part = |gui: &mut Self, class: &str, text: Option<String>, size: Option<Values>| {
+            let mut element = Element::new().with_class(class);
+            element.set_text(text);
+            let element = gui.add_element(element);
+            elements.push(element);
+            Section { element, size }
+        };
+        let square = || Some(Values::container(100.0, Side::Height));
+        let check = part(self, "menu-check", None, square());
+        let label = part(self, "menu-label", Some(text.clone()), None);
+        let shortcut = part(
+            self,
+            "menu-shortcut",
+            shortcut.map(|s| s.to_string()),
+            Some(Values::logical(70.0)),
+        );
+        let arrow = part(self, "menu-arrow", submenu.then(|| ">".to_string()), square());
+        if let Some(element) = self.get_element_mut(check.element) {
+            element.styles.visible = checked == Some(true);
+        }
+        let mut element = Element::new()
+            .with_class("menu-item")
+            .with_children(Children::Columns {
+                children: vec![check, label, shortcut, arrow],
+                spacing: None,
+            });
+        if checked == Some(true) {
+            element.add_class("checked");
+        }
+        self.add_element(element)
+    }
+
+    /// Closes open menus from `level` deeper
+    fn truncate_menus(&mut self, level: usize) {
+        if level >= self.menus.open.len() {
+            return;
+        }
+        for open in self.menus.open.split_off(level) {
+            if let Some(layer) = self.menus.layer.and_then(|l| self.get_element_mut(l)) {
+                layer.overlays.retain(|overlay| *overlay != open.element);
+            }
+            for element in open.elements {
+                self.remove_element(element);
+            }
+        }
+    }
+
+    /// Adds the default styles of menus, see `Gui::set_default_class`
+    fn default_menu_classes(&mut self) {
+        let theme = |name: &str| Colors::Theme(name.to_string(), None);
+        let text = |color: &str| {
+            let mut styles = Styles::default();
+            styles.text_color.set(theme(color));
+            styles.text_size.set(Values::theme("font_size"));
+            styles
+        };
+
+        let mut bar = Styles::default();
+        bar.bg_color.set(theme("surface"));
+        self.set_default_class("menu-bar", bar);
+        let mut menu = Styles::default();
+        menu.bg_color.set(theme("surface"));
+        menu.edges_radius.set(Values::theme("radius"));
+        self.set_default_class("menu", menu);
+        for class in ["menu-bar-item", "menu-item"] {
+            let mut item = text("text");
+            item.states.hover_mut().bg_color.set(theme("border"));
+            self.set_default_class(class, item);
+        }
+        let mut separator = Styles::default();
+        separator.height.set(Values::logical(1.0));
+        separator.bg_color.set(theme("border"));
+        self.set_default_class("menu-separator", separator);
+        let mut check = Styles::default();
+        check.width.set(Values::container(40.0, Side::Min));
+        check.height.set(Values::container(40.0, Side::Min));
+        check.bg_color.set(theme("accent"));
+        check.edges_radius.set(Values::container(50.0, Side::Min));
+        self.set_default_class("menu-check", check);
+        self.set_default_class("menu-label", text("text"));
+        self.set_default_class("menu-shortcut", text("text_muted"));
+        self.set_default_class("menu-arrow", text("text_muted"));
+    }
+}
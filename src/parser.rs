@@ -0,0 +1,1150 @@
+//! Parser for style sheets written in a small CSS-like language
+//!
+//! ```css
+//! /* classes are selected with a dot, labels with a hash */
+//! .button, #play {
+//!     width: min(50%w, 300px);
+//!     height: 40px;
+//!     bg-color: theme(surface);
+//!     edges-radius: theme(radius);
+//!     transition: bg-color 0.2s ease-out;
+//! }
+//!
+//! .button:hover {
+//!     bg-color: #3366ff;
+//! }
+//! ```
+//!
+//! Units of values:
+//! - `px` or no unit - physical pixels
+//! - `lpx` - logical pixels, scaled by the scale factor of the `Gui`
+//! - `%` - percentage of the container, side depends on the property
+//! - `%w`, `%h`, `%min`, `%max`, `%d` - percentage of a side of the container
+//! - `%cw`, `%vw`, `%iw`, ... - percentage of a side of the container, view port or image
+//! - `vw`, `vh`, `vmin`, `vmax` - percentage of the view port
+//!
+//! Values can be combined using `+ - * / %` and the functions
+//! `min`, `max`, `pow`, `round`, `floor`, `ceil`, `sqrt` and `abs`.
+//! `theme(name)` references an entry of the current `Theme`,
+//! colors accept an alpha as well: `theme(accent, 0.5)`.
+
+use std::{
+    fmt,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::SystemTime,
+};
+
+use crate::{
+    animation::{Easing, Transition, Transitions},
+    texture::{Fit, ImageAlign, NineSlice, SliceInset, Tiling, UvRect},
+    styles::styles_proposition::{
+        AspectRatio, Colors, Functions, Op, Position, PositionValues, RValue, Rotation, Round,
+        Side, StyleSheet, Styles, Value, Values,
+    },
+    Gui,
+};
+
+/// Error produced while parsing styles
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// Line of the error, starting at 1
+    pub line: usize,
+    /// Column of the error, starting at 1
+    pub column: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Error produced while loading a style sheet from a file
+#[derive(Debug)]
+pub enum LoadError {
+    Io(std::io::Error),
+    Parse(ParseError),
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Io(err) => write!(f, "could not read style sheet: {}", err),
+            LoadError::Parse(err) => write!(f, "could not parse style sheet: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
+
+impl From<std::io::Error> for LoadError {
+    fn from(err: std::io::Error) -> Self {
+        LoadError::Io(err)
+    }
+}
+
+impl From<ParseError> for LoadError {
+    fn from(err: ParseError) -> Self {
+        LoadError::Parse(err)
+    }
+}
+
+impl StyleSheet {
+    /// Parses a style sheet
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        let mut sheet = StyleSheet::new();
+        Parser::new(source).sheet(&mut sheet)?;
+        Ok(sheet)
+    }
+
+    /// Reads and parses a style sheet file
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LoadError> {
+        let source = std::fs::read_to_string(path)?;
+        Ok(Self::parse(&source)?)
+    }
+}
+
+impl Values {
+    /// Parses an expression like `"50%cw - 20px"`
+    ///
+    /// Percentages have to name the side they are taken from, see the module documentation
+    pub fn parse(source: &str) -> Result<Self, ParseError> {
+        Parser::new(source).values_only()
+    }
+}
+
+impl FromStr for Values {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Values::parse(s)
+    }
+}
+
+/// Reloads a style sheet file whenever it changes
+///
+/// ```ignore
+/// let mut watcher = StyleWatcher::new("ui.css");
+/// // every frame
+/// if let Err(err) = watcher.poll(&mut gui) {
+///     eprintln!("{}", err);
+/// }
+/// ```
+pub struct StyleWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+impl StyleWatcher {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            modified: None,
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Loads the style sheet into `gui` if the file was modified since the last call
+    ///
+    /// Returns true if the style sheet was reloaded. On error the current
+    /// style sheet is kept and the file is retried after its next modification.
+    pub fn poll<Msg: Clone>(&mut self, gui: &mut Gui<Msg>) -> Result<bool, LoadError> {
+        let modified = std::fs::metadata(&self.path)?.modified()?;
+        if self.modified == Some(modified) {
+            return Ok(false);
+        }
+        self.modified = Some(modified);
+        gui.set_style_sheet(StyleSheet::load(&self.path)?);
+        Ok(true)
+    }
+}
+
+/// Selects which styles a rule applies to
+enum Selector {
+    Class(String),
+    Label(String),
+}
+
+/// Which state styles a rule applies to
+#[derive(Clone, Copy)]
+enum State {
+    Normal,
+    Hover,
+    Focus,
+    Active,
+    Disabled,
+}
+
+pub(crate) struct Parser<'a> {
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    pub fn new(source: &'a str) -> Self {
+        Self { source, pos: 0 }
+    }
+
+    fn error<T>(&self, message: impl Into<String>) -> Result<T, ParseError> {
+        self.error_at(self.pos, message)
+    }
+
+    fn error_at<T>(&self, pos: usize, message: impl Into<String>) -> Result<T, ParseError> {
+        let before = &self.source[..pos];
+        let line = before.matches('\n').count() + 1;
+        let column = before
+            .rfind('\n')
+            .map(|i| before[i + 1..].chars().count())
+            .unwrap_or(before.chars().count())
+            + 1;
+        Err(ParseError {
+            line,
+            column,
+            message: message.into(),
+        })
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.source[self.pos..].chars().next()
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    /// Skips whitespace and comments
+    fn skip(&mut self) {
+        loop {
+            let rest = &self.source[self.pos..];
+            if rest.starts_with("/*") {
+                match rest.find("*/") {
+                    Some(end) => self.pos += end + 2,
+                    None => self.pos = self.source.len(),
+                }
+            } else if rest.starts_with("//") {
+                match rest.find('\n') {
+                    Some(end) => self.pos += end + 1,
+                    None => self.pos = self.source.len(),
+                }
+            } else if rest.starts_with(char::is_whitespace) {
+                self.bump();
+            } else {
+                return;
+            }
+        }
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        self.skip();
+        if self.peek() == Some(c) {
+            self.bump();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), ParseError> {
+        if self.eat(c) {
+            Ok(())
+        } else {
+            match self.peek() {
+                Some(found) => self.error(format!("expected `{}`, found `{}`", c, found)),
+                None => self.error(format!("expected `{}`, found end of input", c)),
+            }
+        }
+    }
+
+    fn ident(&mut self) -> Result<String, ParseError> {
+        self.skip();
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_alphanumeric() || c == '-' || c == '_' {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        if start == self.pos {
+            return self.error("expected identifier");
+        }
+        Ok(self.source[start..self.pos].to_string())
+    }
+
+    /// Returns the identifier at the current position without consuming it
+    fn peek_ident(&mut self) -> Option<String> {
+        self.skip();
+        let start = self.pos;
+        let ident = self.ident().ok();
+        self.pos = start;
+        ident
+    }
+
+    /// Consumes the next identifier if it is exactly `keyword`
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        if self.peek_ident().as_deref() == Some(keyword) {
+            self.pos += keyword.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn number(&mut self) -> Result<f32, ParseError> {
+        self.skip();
+        let start = self.pos;
+        if self.peek() == Some('-') {
+            self.bump();
+        }
+        while let Some(c) = self.peek() {
+            if c.is_ascii_digit() || c == '.' {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        match self.source[start..self.pos].parse() {
+            Ok(number) => Ok(number),
+            Err(_) => self.error_at(start, "expected number"),
+        }
+    }
+
+    /// Reads the unit right after a number, whitespace ends the number
+    fn suffix(&mut self) -> String {
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_alphabetic() {
+                self.bump();
+            } else {
+                break;
+            }
+        }
+        self.source[start..self.pos].to_string()
+    }
+
+    fn at_end(&mut self) -> bool {
+        self.skip();
+        self.pos >= self.source.len()
+    }
+
+    /// Returns true if the current declaration has ended
+    fn at_value_end(&mut self) -> bool {
+        self.skip();
+        matches!(self.peek(), None | Some(';') | Some('}'))
+    }
+
+    fn sheet(&mut self, sheet: &mut StyleSheet) -> Result<(), ParseError> {
+        while !self.at_end() {
+            self.rule(sheet)?;
+        }
+        Ok(())
+    }
+
+    fn rule(&mut self, sheet: &mut StyleSheet) -> Result<(), ParseError> {
+        let mut selectors = Vec::new();
+        loop {
+            selectors.push(self.selector()?);
+            if !self.eat(',') {
+                break;
+            }
+        }
+        self.expect('{')?;
+        loop {
+            if self.eat('}') {
+                return Ok(());
+            }
+            let start = self.pos;
+            let property = self.ident()?;
+            self.expect(':')?;
+            let value_start = self.pos;
+            for (selector, state) in &selectors {
+                self.pos = value_start;
+                let styles = match selector {
+                    Selector::Class(name) => sheet.class_entry(name),
+                    Selector::Label(label) => sheet.label_entry(label),
+                };
+                let styles = match state {
+                    State::Normal => styles,
+                    State::Hover => styles.states.hover_mut(),
+                    State::Focus => styles.states.focus_mut(),
+                    State::Active => styles.states.active_mut(),
+                    State::Disabled => styles.states.disabled_mut(),
+                };
+                self.declaration(styles, &property, start)?;
+            }
+            if !self.at_value_end() {
+                return self.error("expected `;`");
+            }
+            if !self.eat(';') {
+                self.skip();
+                if self.peek() != Some('}') {
+                    return self.error("expected `;`");
+                }
+            }
+        }
+    }
+
+    fn selector(&mut self) -> Result<(Selector, State), ParseError> {
+        self.skip();
+        let selector = match self.bump() {
+            Some('.') => Selector::Class(self.ident()?),
+            Some('#') => Selector::Label(self.ident()?),
+            _ => return self.error("expected selector starting with `.` or `#`"),
+        };
+        if self.peek() != Some(':') {
+            return Ok((selector, State::Normal));
+        }
+        self.bump();
+        let start = self.pos;
+        let state = match self.ident()?.as_str() {
+            "hover" => State::Hover,
+            "focus" => State::Focus,
+            "active" => State::Active,
+            "disabled" => State::Disabled,
+            state => return self.error_at(start, format!("unknown state `{}`", state)),
+        };
+        Ok((selector, state))
+    }
+
+    fn declaration(
+        &mut self,
+        styles: &mut Styles,
+        property: &str,
+        start: usize,
+    ) -> Result<(), ParseError> {
+        match property {
+            "width" => styles.width.set(self.values(Side::Width)?),
+            "height" => styles.height.set(self.values(Side::Height)?),
+            "min-width" => styles.min_width.set(self.limit(Side::Width)?),
+            "max-width" => styles.max_width.set(self.limit(Side::Width)?),
+            "min-height" => styles.min_height.set(self.limit(Side::Height)?),
+            "max-height" => styles.max_height.set(self.limit(Side::Height)?),
+            "aspect-ratio" => styles.aspect_ratio.set(self.aspect_ratio()?),
+            "round" => styles.round.set(self.round()?),
+            "nine-slice" => styles.nine_slice.set(self.nine_slice()?),
+            "fit" | "object-fit" => styles.fit.set(self.fit()?),
+            "image-align" | "object-position" => styles.image_align.set(self.image_align()?),
+            "uv-rect" => styles.uv_rect.set(self.uv_rect()?),
+            "tiling" => styles.tiling.set(self.tiling()?),
+            "margin" => styles.margin.set(self.values(Side::Min)?),
+            "padding" => styles.padding.set(self.values(Side::Min)?),
+            "text-size" | "font-size" => styles.text_size.set(self.values(Side::Min)?),
+            "edges-radius" | "border-radius" => styles.edges_radius.set(self.values(Side::Min)?),
+            "edges-smooth" => styles.edges_smooth.set(self.values(Side::Min)?),
+            "bg-color" | "background-color" | "background" => styles.bg_color.set(self.color()?),
+            "text-color" | "color" => styles.text_color.set(self.color()?),
+            "alpha" | "opacity" => styles.alpha.set(self.number()?),
+            "rotation" => styles.rotation.set(self.rotation()?),
+            "font" | "font-family" => styles.font.set(self.font()?),
+            "position" => styles.position.set(self.position()?),
+            "transition" => styles.transitions.set(self.transitions()?),
+            _ => return self.error_at(start, format!("unknown property `{}`", property)),
+        }
+        Ok(())
+    }
+
+    /// Parses a `Values` expression
+    ///
+    /// `side` is used for percentages without an explicit side
+    pub fn values(&mut self, side: Side) -> Result<Values, ParseError> {
+        self.sum(Some(&side))
+    }
+
+    /// Parses a `Values` expression that has to span the whole source
+    fn values_only(&mut self) -> Result<Values, ParseError> {
+        let values = self.sum(None)?;
+        match self.at_end() {
+            true => Ok(values),
+            false => self.error(format!("unexpected `{}`", self.peek().unwrap_or_default())),
+        }
+    }
+
+    fn sum(&mut self, side: Option<&Side>) -> Result<Values, ParseError> {
+        let mut left = self.product(side)?;
+        loop {
+            let op = if self.eat('+') {
+                Op::Add
+            } else if self.eat('-') {
+                Op::Sub
+            } else {
+                return Ok(left);
+            };
+            let right = self.product(side)?;
+            left = Values::expr(left, op, right);
+        }
+    }
+
+    fn product(&mut self, side: Option<&Side>) -> Result<Values, ParseError> {
+        let mut left = self.unary(side)?;
+        loop {
+            let op = if self.eat('*') {
+                Op::Mul
+            } else if self.eat('/') {
+                Op::Div
+            } else if self.eat('%') {
+                Op::Mod
+            } else {
+                return Ok(left);
+            };
+            let right = self.unary(side)?;
+            left = Values::expr(left, op, right);
+        }
+    }
+
+    fn unary(&mut self, side: Option<&Side>) -> Result<Values, ParseError> {
+        if self.eat('-') {
+            let value = self.unary(side)?;
+            return Ok(-value);
+        }
+        self.primary(side)
+    }
+
+    fn primary(&mut self, side: Option<&Side>) -> Result<Values, ParseError> {
+        self.skip();
+        match self.peek() {
+            Some('(') => {
+                self.bump();
+                let value = self.sum(side)?;
+                self.expect(')')?;
+                Ok(value)
+            }
+            Some(c) if c.is_ascii_digit() || c == '.' => self.unit(side),
+            Some(c) if c.is_alphabetic() => self.function(side),
+            Some(c) => self.error(format!("expected value, found `{}`", c)),
+            None => self.error("expected value, found end of input"),
+        }
+    }
+
+    fn unit(&mut self, side: Option<&Side>) -> Result<Values, ParseError> {
+        let number = self.number()?;
+        let start = self.pos;
+        let percent = self.peek() == Some('%')
+            && self.source[self.pos + 1..]
+                .chars()
+                .next()
+                .map(|c| c.is_alphabetic() || c.is_whitespace() || ",;)}+-*/".contains(c))
+                .unwrap_or(true);
+        if percent {
+            self.bump();
+        }
+        let unit = self.suffix();
+        let value = match (percent, unit.as_str()) {
+            (false, "") | (false, "px") => Value::Pixel(number),
+            (false, "lpx") => Value::Logical(number),
+            (false, "vw") => Value::ViewPort(RValue::Percent(number), Side::Width),
+            (false, "vh") => Value::ViewPort(RValue::Percent(number), Side::Height),
+            (false, "vmin") => Value::ViewPort(RValue::Percent(number), Side::Min),
+            (false, "vmax") => Value::ViewPort(RValue::Percent(number), Side::Max),
+            (true, "") => match side {
+                Some(side) => Value::Container(RValue::Percent(number), side.clone()),
+                None => {
+                    return self.error_at(
+                        start,
+                        "percentage needs a side, for example `50%w` or `50%vh`",
+                    )
+                }
+            },
+            (true, unit) => {
+                let (target, side) = match (unit.get(..1), unit.get(1..)) {
+                    (Some("c"), Some(side)) if Self::side(side).is_some() => ('c', side),
+                    (Some("v"), Some(side)) if Self::side(side).is_some() => ('v', side),
+                    (Some("i"), Some(side)) if Self::side(side).is_some() => ('i', side),
+                    _ => ('c', unit),
+                };
+                let side = match Self::side(side) {
+                    Some(side) => side,
+                    None => return self.error_at(start, format!("unknown unit `%{}`", unit)),
+                };
+                match target {
+                    'v' => Value::ViewPort(RValue::Percent(number), side),
+                    'i' => Value::Image(RValue::Percent(number), side),
+                    _ => Value::Container(RValue::Percent(number), side),
+                }
+            }
+            (false, unit) => return self.error_at(start, format!("unknown unit `{}`", unit)),
+        };
+        Ok(Values::Value(value))
+    }
+
+    fn side(side: &str) -> Option<Side> {
+        match side {
+            "w" => Some(Side::Width),
+            "h" => Some(Side::Height),
+            "min" => Some(Side::Min),
+            "max" => Some(Side::Max),
+            "d" => Some(Side::Diameter),
+            _ => None,
+        }
+    }
+
+    fn function(&mut self, side: Option<&Side>) -> Result<Values, ParseError> {
+        let start = self.pos;
+        let name = self.ident()?;
+        if name == "theme" {
+            self.expect('(')?;
+            let name = self.ident()?;
+            self.expect(')')?;
+            return Ok(Values::Value(Value::Theme(name)));
+        }
+        let op = match name.as_str() {
+            "min" => Some(Op::Min),
+            "max" => Some(Op::Max),
+            "pow" => Some(Op::Pow),
+            _ => None,
+        };
+        if let Some(op) = op {
+            self.expect('(')?;
+            let left = self.sum(side)?;
+            self.expect(',')?;
+            let right = self.sum(side)?;
+            self.expect(')')?;
+            return Ok(Values::expr(left, op, right));
+        }
+        let fun = match name.as_str() {
+            "round" => Functions::Round,
+            "floor" => Functions::Floor,
+            "ceil" => Functions::Ceil,
+            "sqrt" => Functions::Sqrt,
+            "abs" => Functions::Abs,
+            "calc" => {
+                self.expect('(')?;
+                let value = self.sum(side)?;
+                self.expect(')')?;
+                return Ok(value);
+            }
+            _ => return self.error_at(start, format!("unknown function `{}`", name)),
+        };
+        self.expect('(')?;
+        let value = self.sum(side)?;
+        self.expect(')')?;
+        Ok(Values::function(fun, value))
+    }
+
+    fn color(&mut self) -> Result<Colors, ParseError> {
+        self.skip();
+        let start = self.pos;
+        if self.eat('#') {
+            let hex = self.ident()?;
+            let digits = hex
+                .chars()
+                .map(|c| c.to_digit(16).map(|d| d as f32))
+                .collect::<Option<Vec<f32>>>();
+            let color = match (digits, hex.len()) {
+                (Some(d), 3) => Colors::Rgb(d[0] / 15.0, d[1] / 15.0, d[2] / 15.0),
+                (Some(d), 6) => Colors::Rgb(
+                    (d[0] * 16.0 + d[1]) / 255.0,
+                    (d[2] * 16.0 + d[3]) / 255.0,
+                    (d[4] * 16.0 + d[5]) / 255.0,
+                ),
+                (Some(d), 8) => Colors::Rgba(
+                    (d[0] * 16.0 + d[1]) / 255.0,
+                    (d[2] * 16.0 + d[3]) / 255.0,
+                    (d[4] * 16.0 + d[5]) / 255.0,
+                    (d[6] * 16.0 + d[7]) / 255.0,
+                ),
+                _ => return self.error_at(start, format!("invalid hex color `#{}`", hex)),
+            };
+            return Ok(color);
+        }
+        let name = self.ident()?;
+        let color = match name.as_str() {
+            "rgb" | "rgba" | "hsl" | "cmyk" => {
+                self.expect('(')?;
+                let mut args = vec![self.number()?];
+                while self.eat(',') {
+                    args.push(self.number()?);
+                }
+                self.expect(')')?;
+                match (name.as_str(), args.as_slice()) {
+                    ("rgb", [r, g, b]) => Colors::Rgb(r / 255.0, g / 255.0, b / 255.0),
+                    ("rgba", [r, g, b, a]) => Colors::Rgba(r / 255.0, g / 255.0, b / 255.0, *a),
+                    ("hsl", [h, s, l]) => Colors::Hsl(*h, *s, *l),
+                    ("cmyk", [c, m, y, k]) => Colors::Cmyk(*c, *m, *y, *k),
+                    _ => {
+                        return self
+                            .error_at(start, format!("wrong number of arguments for `{}`", name))
+                    }
+                }
+            }
+            "theme" => {
+                self.expect('(')?;
+                let name = self.ident()?;
+                let alpha = if self.eat(',') { Some(self.number()?) } else { None };
+                self.expect(')')?;
+                Colors::Theme(name, alpha)
+            }
+            "transparent" => Colors::TRANSPARENT,
+            "white" => Colors::WHITE,
+            "black" => Colors::BLACK,
+            "red" => Colors::RED,
+            "green" => Colors::GREEN,
+            "blue" => Colors::BLUE,
+            "yellow" => Colors::YELLOW,
+            "cyan" => Colors::CYAN,
+            "magenta" => Colors::MAGENTA,
+            "gray" => Colors::GRAY,
+            "light-gray" => Colors::LIGHT_GRAY,
+            "dark-gray" => Colors::DARK_GRAY,
+            _ => return self.error_at(start, format!("unknown color `{}`", name)),
+        };
+        Ok(color)
+    }
+
+    fn rotation(&mut self) -> Result<Rotation, ParseError> {
+        self.skip();
+        let start = self.pos;
+        if self.peek().map(|c| c.is_alphabetic()).unwrap_or(false) {
+            return match self.ident()?.as_str() {
+                "none" => Ok(Rotation::None),
+                name => self.error_at(start, format!("unknown rotation `{}`", name)),
+            };
+        }
+        let number = self.number()?;
+        let unit_start = self.pos;
+        match self.suffix().as_str() {
+            "" | "deg" => Ok(Rotation::Deg(number)),
+            "rad" => Ok(Rotation::Rad(number)),
+            unit => self.error_at(unit_start, format!("unknown rotation unit `{}`", unit)),
+        }
+    }
+
+    fn font(&mut self) -> Result<Option<String>, ParseError> {
+        self.skip();
+        if self.eat('"') {
+            let start = self.pos;
+            while !matches!(self.peek(), Some('"') | None) {
+                self.bump();
+            }
+            let font = self.source[start..self.pos].to_string();
+            self.expect('"')?;
+            return Ok(Some(font));
+        }
+        match self.ident()?.as_str() {
+            "default" | "none" => Ok(None),
+            font => Ok(Some(font.to_string())),
+        }
+    }
+
+    /// Parses `<anchor> [viewport] [<x>[,] <y>] [pivot <anchor>]`
+    ///
+    /// The x offset ends before a `+` or `-` so that `10px -20px` is two offsets,
+    /// wrap sums in parentheses like `(50%w - 10px) 20px`
+    fn position(&mut self) -> Result<Position, ParseError> {
+        let mut position = Position::new(self.anchor()?);
+        if self.eat_keyword("viewport") {
+            position = position.in_view_port();
+        }
+        self.skip();
+        if !self.at_value_end() && self.peek_ident().as_deref() != Some("pivot") {
+            let x = self.product(Some(&Side::Width))?;
+            self.eat(',');
+            let y = self.values(Side::Height)?;
+            position = position.with_offset(x, y);
+        }
+        if self.eat_keyword("pivot") {
+            position = position.with_pivot(self.anchor()?);
+        }
+        Ok(position)
+    }
+
+    /// Parses a named anchor or `fraction(<x>, <y>)`
+    fn anchor(&mut self) -> Result<PositionValues, ParseError> {
+        let start = self.pos;
+        let anchor = match self.ident()?.as_str() {
+            "top" => PositionValues::Top,
+            "top-left" => PositionValues::TopLeft,
+            "top-right" => PositionValues::TopRight,
+            "center" => PositionValues::Center,
+            "left" | "center-left" => PositionValues::CenterLeft,
+            "right" | "center-right" => PositionValues::CenterRight,
+            "bottom" => PositionValues::Bottom,
+            "bottom-left" => PositionValues::BottomLeft,
+            "bottom-right" => PositionValues::BottomRight,
+            "fraction" => {
+                self.expect('(')?;
+                let x = self.number()?;
+                self.expect(',')?;
+                let y = self.number()?;
+                self.expect(')')?;
+                PositionValues::Fraction(x, y)
+            }
+            anchor => return self.error_at(start, format!("unknown anchor `{}`", anchor)),
+        };
+        Ok(anchor)
+    }
+
+    /// Parses `none` or a value
+    fn limit(&mut self, side: Side) -> Result<Option<Values>, ParseError> {
+        if self.eat_keyword("none") {
+            return Ok(None);
+        }
+        Ok(Some(self.values(side)?))
+    }
+
+    /// Parses `none`, `image`, `<width> / <height>` or `<ratio>`
+    fn aspect_ratio(&mut self) -> Result<AspectRatio, ParseError> {
+        self.skip();
+        if self.peek().map(|c| c.is_alphabetic()).unwrap_or(false) {
+            let start = self.pos;
+            return match self.ident()?.as_str() {
+                "none" => Ok(AspectRatio::None),
+                "image" => Ok(AspectRatio::Image),
+                ratio => self.error_at(start, format!("unknown aspect ratio `{}`", ratio)),
+            };
+        }
+        let start = self.pos;
+        let mut ratio = self.number()?;
+        if self.eat('/') {
+            self.skip();
+            let height_start = self.pos;
+            let height = self.number()?;
+            if height == 0.0 {
+                return self.error_at(height_start, "height of an aspect ratio can not be zero");
+            }
+            ratio /= height;
+        }
+        if ratio <= 0.0 {
+            return self.error_at(start, "aspect ratio has to be positive");
+        }
+        Ok(AspectRatio::Ratio(ratio))
+    }
+
+    /// Parses `auto` for the rounding of the `Gui` or a rounding mode
+    fn round(&mut self) -> Result<Option<Round>, ParseError> {
+        let start = self.pos;
+        match self.ident()?.as_str() {
+            "auto" => Ok(None),
+            "none" => Ok(Some(Round::None)),
+            "floor" => Ok(Some(Round::Floor)),
+            "nearest" => Ok(Some(Round::Nearest)),
+            "device-pixel" => Ok(Some(Round::DevicePixel)),
+            round => self.error_at(start, format!("unknown rounding `{}`", round)),
+        }
+    }
+
+    /// Parses `none` or `<inset>{1,4} [scale <number>]`
+    ///
+    /// Insets are in pixels of the texture or percentages of it and are ordered
+    /// top, right, bottom, left with missing sides copied like in CSS
+    fn nine_slice(&mut self) -> Result<Option<NineSlice>, ParseError> {
+        self.skip();
+        if self.peek().map(|c| c.is_alphabetic()).unwrap_or(false) {
+            let start = self.pos;
+            return match self.ident()?.as_str() {
+                "none" => Ok(None),
+                slice => self.error_at(start, format!("unknown nine-slice `{}`", slice)),
+            };
+        }
+        let mut insets = Vec::new();
+        loop {
+            self.skip();
+            if insets.len() == 4 || !matches!(self.peek(), Some(c) if c.is_ascii_digit() || c == '.') {
+                break;
+            }
+            let number = self.number()?;
+            let inset = if self.peek() == Some('%') {
+                self.bump();
+                SliceInset::Fraction(number / 100.0)
+            } else {
+                let unit_start = self.pos;
+                match self.suffix().as_str() {
+                    "" | "px" => SliceInset::Pixels(number),
+                    unit => {
+                        return self
+                            .error_at(unit_start, format!("unknown nine-slice unit `{}`", unit))
+                    }
+                }
+            };
+            insets.push(inset);
+        }
+        let [top, right, bottom, left] = match insets[..] {
+            [] => return self.error("expected nine-slice inset"),
+            [all] => [all; 4],
+            [vertical, horizontal] => [vertical, horizontal, vertical, horizontal],
+            [top, horizontal, bottom] => [top, horizontal, bottom, horizontal],
+            [top, right, bottom, left, ..] => [top, right, bottom, left],
+        };
+        let mut slice = NineSlice::new(left, top, right, bottom);
+        if !self.at_value_end() {
+            let start = self.pos;
+            match self.ident()?.as_str() {
+                "scale" => slice.scale = self.number()?,
+                word => return self.error_at(start, format!("unexpected `{}`", word)),
+            }
+        }
+        Ok(Some(slice))
+    }
+
+    /// Parses `fill`, `contain`, `cover`, `none` or `scale-down`
+    fn fit(&mut self) -> Result<Fit, ParseError> {
+        let start = self.pos;
+        match self.ident()?.as_str() {
+            "fill" => Ok(Fit::Fill),
+            "contain" => Ok(Fit::Contain),
+            "cover" => Ok(Fit::Cover),
+            "none" => Ok(Fit::None),
+            "scale-down" => Ok(Fit::ScaleDown),
+            fit => self.error_at(start, format!("unknown fit `{}`", fit)),
+        }
+    }
+
+    /// Parses one or two of `left`, `center`, `right`, `top`, `bottom` or `<percent>%`
+    ///
+    /// Percentages are horizontal first, like in CSS the other axis defaults to center
+    fn image_align(&mut self) -> Result<ImageAlign, ParseError> {
+        let mut align = ImageAlign::CENTER;
+        let mut percents = 0;
+        for _ in 0..2 {
+            if self.at_value_end() {
+                break;
+            }
+            let start = self.pos;
+            if self.peek().map(|c| c.is_alphabetic()).unwrap_or(false) {
+                match self.ident()?.as_str() {
+                    "left" => align.x = 0.0,
+                    "right" => align.x = 1.0,
+                    "top" => align.y = 0.0,
+                    "bottom" => align.y = 1.0,
+                    "center" => (),
+                    align => return self.error_at(start, format!("unknown alignment `{}`", align)),
+                }
+            } else {
+                let percent = self.number()? / 100.0;
+                self.expect('%')?;
+                match percents {
+                    0 => align.x = percent,
+                    _ => align.y = percent,
+                }
+                percents += 1;
+            }
+        }
+        Ok(align)
+    }
+
+    /// Parses `none` or `<x> <y> <width> <height>` in texture coordinates
+    fn uv_rect(&mut self) -> Result<UvRect, ParseError> {
+        self.skip();
+        if self.peek().map(|c| c.is_alphabetic()).unwrap_or(false) {
+            let start = self.pos;
+            return match self.ident()?.as_str() {
+                "none" => Ok(UvRect::FULL),
+                rect => self.error_at(start, format!("unknown uv rect `{}`", rect)),
+            };
+        }
+        Ok(UvRect::new(
+            self.number()?,
+            self.number()?,
+            self.number()?,
+            self.number()?,
+        ))
+    }
+
+    /// Parses `none`, `repeat` or `mirror`
+    fn tiling(&mut self) -> Result<Tiling, ParseError> {
+        let start = self.pos;
+        match self.ident()?.as_str() {
+            "none" => Ok(Tiling::None),
+            "repeat" => Ok(Tiling::Repeat),
+            "mirror" => Ok(Tiling::Mirror),
+            tiling => self.error_at(start, format!("unknown tiling `{}`", tiling)),
+        }
+    }
+
+    /// Parses `<property> <duration>(s|ms) [easing], ...`
+    fn transitions(&mut self) -> Result<Transitions, ParseError> {
+        let mut transitions = Transitions::default();
+        loop {
+            self.skip();
+            let start = self.pos;
+            let property = self.ident()?;
+            let duration = self.number()?;
+            let unit_start = self.pos;
+            let duration = match self.suffix().as_str() {
+                "s" => duration,
+                "ms" => duration / 1000.0,
+                "" => return self.error_at(unit_start, "expected time unit `s` or `ms`"),
+                unit => {
+                    return self.error_at(unit_start, format!("unknown time unit `{}`", unit))
+                }
+            };
+            self.skip();
+            let easing = if self.peek().map(|c| c.is_alphabetic()).unwrap_or(false) {
+                let start = self.pos;
+                match self.ident()?.as_str() {
+                    "linear" => Easing::Linear,
+                    "ease-in" => Easing::EaseIn,
+                    "ease-out" => Easing::EaseOut,
+                    "ease" | "ease-in-out" => Easing::EaseInOut,
+                    "ease-out-back" => Easing::EaseOutBack,
+                    easing => return self.error_at(start, format!("unknown easing `{}`", easing)),
+                }
+            } else {
+                Easing::Linear
+            };
+            let transition = Some(Transition::new(duration, easing));
+            match property.as_str() {
+                "all" => transitions = Transitions::all(Transition::new(duration, easing)),
+                "width" => transitions.width = transition,
+                "height" => transitions.height = transition,
+                "position" => transitions.position = transition,
+                "rotation" => transitions.rotation = transition,
+                "bg-color" | "background-color" | "background" => transitions.bg_color = transition,
+                "text-color" | "color" => transitions.text_color = transition,
+                "alpha" | "opacity" => transitions.alpha = transition,
+                "edges-radius" | "border-radius" => transitions.edges_radius = transition,
+                "edges-smooth" => transitions.edges_smooth = transition,
+                "text-size" | "font-size" => transitions.text_size = transition,
+                _ => return self.error_at(start, format!("unknown property `{}`", property)),
+            }
+            if !self.eat(',') {
+                return Ok(transitions);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn transitions(source: &str) -> Result<Transitions, ParseError> {
+        Parser::new(source).transitions()
+    }
+
+    fn error<T>(result: Result<T, ParseError>) -> (usize, usize, String) {
+        match result {
+            Ok(_) => panic!("expected an error"),
+            Err(err) => (err.line, err.column, err.message),
+        }
+    }
+
+    #[test]
+    fn unitless_duration_is_rejected() {
+        assert_eq!(
+            error(transitions("bg-color 200")),
+            (1, 13, "expected time unit `s` or `ms`".to_string())
+        );
+    }
+
+    #[test]
+    fn units_and_easings_of_a_list() {
+        let transitions = transitions("width 200ms ease-in, height 2s, alpha 1s ease").unwrap();
+        assert_eq!(transitions.width.unwrap().duration, 0.2);
+        assert!(matches!(transitions.width.unwrap().easing, Easing::EaseIn));
+        assert_eq!(transitions.height.unwrap().duration, 2.0);
+        assert!(matches!(transitions.alpha.unwrap().easing, Easing::EaseInOut));
+    }
+
+    #[test]
+    fn unit_separated_by_whitespace_is_missing() {
+        assert_eq!(
+            error(transitions("bg-color 200 ms")),
+            (1, 13, "expected time unit `s` or `ms`".to_string())
+        );
+    }
+
+    #[test]
+    fn unknown_time_unit_points_at_the_unit() {
+        assert_eq!(
+            error(transitions("bg-color 2min")),
+            (1, 11, "unknown time unit `min`".to_string())
+        );
+    }
+
+    #[test]
+    fn unknown_property_points_at_the_property() {
+        assert_eq!(
+            error(transitions("width 1s,  colour 1s")),
+            (1, 12, "unknown property `colour`".to_string())
+        );
+    }
+
+    #[test]
+    fn nine_slice_scale_after_unitless_inset() {
+        let slice = Parser::new("10 scale 2").nine_slice().unwrap().unwrap();
+        assert_eq!(slice, NineSlice::pixels(10.0, 10.0, 10.0, 10.0).with_scale(2.0));
+    }
+
+    #[test]
+    fn nine_slice_units() {
+        let slice = Parser::new("4px 25%").nine_slice().unwrap().unwrap();
+        assert_eq!(slice.top, SliceInset::Pixels(4.0));
+        assert_eq!(slice.left, SliceInset::Fraction(0.25));
+        assert_eq!(
+            error(Parser::new("4pt").nine_slice()),
+            (1, 2, "unknown nine-slice unit `pt`".to_string())
+        );
+    }
+
+    /// Offset of the top left corner from the top left of a 200x100 container
+    fn offset(source: &str) -> (f32, f32) {
+        use crate::{
+            styles::styles_proposition::{Container, ViewPort},
+            Point,
+        };
+        let position = Parser::new(source).position().unwrap();
+        let container = Container {
+            position: Point::new(100.0, 50.0),
+            size: Point::new(200.0, 100.0),
+            rotation: 0.0,
+            image: None,
+        };
+        let view_port = ViewPort::new(200.0, 100.0);
+        let point = position.calc(&container, &view_port, Point::new(0.0, 0.0));
+        (point.x, point.y)
+    }
+
+    #[test]
+    fn negative_position_offsets() {
+        assert_eq!(offset("top-left 10px -20px"), (10.0, -20.0));
+        assert_eq!(offset("top-left -10px -20px"), (-10.0, -20.0));
+        assert_eq!(offset("top-left -10px, -20px"), (-10.0, -20.0));
+    }
+
+    #[test]
+    fn position_offsets_with_expressions() {
+        assert_eq!(offset("top-left (50% - 10px) 20px * 2"), (90.0, 40.0));
+        assert_eq!(offset("top-left 10px 50% - 5px"), (10.0, 45.0));
+    }
+
+    #[test]
+    fn keywords_match_whole_identifiers() {
+        assert!(Parser::new("none").limit(Side::Width).unwrap().is_none());
+        assert!(Parser::new("nonexistent").limit(Side::Width).is_err());
+        assert!(Parser::new("top-left viewports").position().is_err());
+        assert!(Parser::new("top-left pivots").position().is_err());
+        assert_eq!(offset("top-left viewport 10px 20px pivot center"), (10.0, 20.0));
+    }
+
+    #[test]
+    fn aspect_ratio_with_zero_height() {
+        assert_eq!(
+            error(Parser::new("16/0").aspect_ratio()),
+            (1, 4, "height of an aspect ratio can not be zero".to_string())
+        );
+    }
+
+    #[test]
+    fn error_position_in_a_sheet() {
+        let source = ".button {\n    transition: bg-color 1x;\n}";
+        assert_eq!(
+            error(StyleSheet::parse(source)),
+            (2, 27, "unknown time unit `x`".to_string())
+        );
+    }
+
+    #[test]
+    fn theme_color_with_alpha() {
+        let color = Parser::new("theme(accent, 0.5)").color().unwrap();
+        assert_eq!(color, Colors::Theme("accent".to_string(), Some(0.5)));
+    }
+}
@@ -25,6 +25,7 @@ pub struct GpuBound {
 pub struct Pipelines {
     pub color_pipeline: wgpu::RenderPipeline,
     pub texture_pipeline: wgpu::RenderPipeline,
+    pub image_pipeline: wgpu::RenderPipeline,
     pub radial_gradient_pipeline: wgpu::RenderPipeline,
     pub linear_gradient_pipeline: wgpu::RenderPipeline,
     pub instancing_pipeline: wgpu::RenderPipeline,
@@ -187,6 +188,46 @@ impl GpuBound {
             cache: None,
         });
 
+        let image_shaders = device.create_shader_module(include_wgsl!("shaders/image.wgsl"));
+
+        let image_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("Image Pipeline"),
+            layout: Some(&texture_pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &image_shaders,
+                entry_point: "vs_main",
+                buffers: &[],
+                compilation_options: Default::default(),
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &image_shaders,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: wgpu::TextureFormat::Bgra8UnormSrgb,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+                compilation_options: Default::default(),
+            }),
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Ccw,
+                cull_mode: None,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                conservative: false,
+                ..Default::default()
+            },
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState {
+                count: 1,
+                mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+            multiview: None,
+            cache: None,
+        });
+
         let radial_gradient_pipeline_layout =
             device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
                 label: Some("Radial Gradient Pipeline Layout"),
@@ -359,6 +400,7 @@ impl GpuBound {
             pipelines: Pipelines {
                 color_pipeline,
                 texture_pipeline,
+                image_pipeline,
                 radial_gradient_pipeline,
                 linear_gradient_pipeline,
                 instancing_pipeline,
@@ -807,6 +849,7 @@ pub struct RenderElement {
     pub rotation_buffer: wgpu::Buffer,
     pub alpha_buffer: wgpu::Buffer,
     pub edges_buffer: wgpu::Buffer,
+    pub texture_params_buffer: wgpu::Buffer,
     pub bind_group: wgpu::BindGroup,
     pub color: Option<RenderColor>,
     pub texture: Option<Arc<Texture>>,
@@ -815,6 +858,22 @@ pub struct RenderElement {
     pub text: Option<Arc<Texture>>,
 }
 
+/// Placement of the background texture inside of the element
+#[repr(C)]
+#[derive(Debug, Clone, Copy, bytemuck::Pod, bytemuck::Zeroable, Default, PartialEq)]
+pub struct TextureParams {
+    /// Left, top, right and bottom border of a nine-slice in texture coordinates
+    pub slice_uv: [f32; 4],
+    /// The same borders in pixels on screen
+    pub slice_px: [f32; 4],
+    /// Part of the texture that is drawn, x, y, width and height
+    pub uv_rect: [f32; 4],
+    /// Fitted image in pixels from the top left corner of the element
+    pub image_rect: [f32; 4],
+    /// 0 for no tiling, 1 to repeat and 2 to mirror, rest is padding
+    pub tiling: [f32; 4],
+}
+
 pub struct RenderColor {
     buffer: wgpu::Buffer,
     bind_group: wgpu::BindGroup,
@@ -1046,6 +1105,16 @@ impl RenderElement {
                 },
                 count: None,
             },
+            wgpu::BindGroupLayoutEntry {
+                binding: 5,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            },
         ],
     };
 
@@ -1085,6 +1154,13 @@ impl RenderElement {
             mapped_at_creation: false,
         });
 
+        let texture_params_buffer = device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Texture Params Buffer"),
+            size: std::mem::size_of::<TextureParams>() as u64,
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
         let bind_group_layout = device.create_bind_group_layout(&Self::LAYOUT);
 
         let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
@@ -1131,6 +1207,14 @@ impl RenderElement {
                         size: None,
                     }),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 5,
+                    resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
+                        buffer: &texture_params_buffer,
+                        offset: 0,
+                        size: None,
+                    }),
+                },
             ],
         });
 
@@ -1140,6 +1224,7 @@ impl RenderElement {
             rotation_buffer,
             alpha_buffer,
             edges_buffer,
+            texture_params_buffer,
             bind_group,
             color: None,
             texture: None,
@@ -1166,6 +1251,14 @@ impl RenderElement {
         self.texture = Some(texture);
     }
 
+    pub fn set_texture_params(&self, params: TextureParams, queue: &wgpu::Queue) {
+        queue.write_buffer(
+            &self.texture_params_buffer,
+            0,
+            bytemuck::cast_slice(&[params]),
+        );
+    }
+
     pub fn set_text(&mut self, text: Arc<Texture>) {
         self.text = Some(text);
     }
@@ -1208,7 +1301,7 @@ impl RenderElement {
             pass.set_bind_group(1, self.bind(), &[]);
         }
         if let Some(texture) = &self.texture {
-            Self::draw_command(&pipelines.texture_pipeline, pass, &texture.bind_group);
+            Self::draw_command(&pipelines.image_pipeline, pass, &texture.bind_group);
         }
         if let Some(radial_gradient) = &self.radial_gradient {
             Self::draw_command(
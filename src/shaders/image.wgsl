@@ -0,0 +1,126 @@
+struct VertexInput {
+    @builtin(vertex_index) index: u32
+}
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) v_tex_coords: vec2<f32>,
+    @location(1) clip_position: vec2<f32>,
+}
+
+// Placement of the texture inside of the element
+struct TextureParams {
+    // Left, top, right and bottom border of a nine-slice in texture coordinates
+    slice_uv: vec4<f32>,
+    // The same borders in pixels on screen
+    slice_px: vec4<f32>,
+    // Part of the texture that is drawn, x, y, width and height
+    uv_rect: vec4<f32>,
+    // Fitted image in pixels from the top left corner of the element
+    image_rect: vec4<f32>,
+    // x: 0 for no tiling, 1 to repeat and 2 to mirror
+    tiling: vec4<f32>,
+}
+
+@group(0)@binding(0) var<uniform> screen_size: vec2<f32>;
+
+@group(1)@binding(0) var<uniform> center: vec2<f32>;
+@group(1)@binding(1) var<uniform> size: vec2<f32>;
+@group(1)@binding(2) var<uniform> rotation: f32;
+@group(1)@binding(3) var<uniform> alpha: f32;
+@group(1)@binding(4) var<uniform> edges: vec2<f32>;
+@group(1)@binding(5) var<uniform> params: TextureParams;
+
+@group(2)@binding(0) var t_diffuse: texture_2d<f32>;
+@group(2)@binding(1) var t_sampler: sampler;
+
+@vertex
+fn vs_main(in: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+
+    // Calculate vertex position
+    var position = vertex_position(in.index);
+    out.v_tex_coords = position + 0.5;
+    out.clip_position = size * position*2.0;
+
+    // Scale and rotate the position
+    var scale = vec2(size.x * position.x, size.y * position.y);
+    var cos_angle = cos(rotation);
+    var sin_angle = sin(rotation);
+    var rotated_position = vec2(
+        scale.x * cos_angle - scale.y * sin_angle,
+        scale.x * sin_angle + scale.y * cos_angle
+    );
+    
+    // Translate to the new position
+    var new_position = center + rotated_position;
+    
+    // Convert to screen space
+    var screen_space = new_position / screen_size * 2.0 - 1.0;
+    var invert_y = vec2(screen_space.x, -screen_space.y);
+
+    out.position = vec4<f32>(invert_y, 0.0, 1.0);
+
+    return out;
+}
+
+@fragment
+fn fs_main(in: VertexOutput) -> @location(0)vec4<f32> {
+    var tile = max(params.image_rect.zw, vec2(0.0001));
+    var local = in.v_tex_coords * size - params.image_rect.xy;
+    var outside = any(local < vec2(0.0)) || any(local > tile);
+    if params.tiling.x > 1.5 {
+        var cell = floor(local / tile);
+        local = local - cell * tile;
+        var flip = abs(cell - 2.0 * floor(cell / 2.0)) > vec2(0.5);
+        local = select(local, tile - local, flip);
+    } else if params.tiling.x > 0.5 {
+        local = local - floor(local / tile) * tile;
+    }
+    var sliced = vec2(
+        slice_axis(local.x, tile.x, params.slice_px.x, params.slice_px.z, params.slice_uv.x, params.slice_uv.z),
+        slice_axis(local.y, tile.y, params.slice_px.y, params.slice_px.w, params.slice_uv.y, params.slice_uv.w),
+    );
+    var uv = params.uv_rect.xy + clamp(sliced, vec2(0.0), vec2(1.0)) * params.uv_rect.zw;
+    var color = textureSample(t_diffuse, t_sampler, uv);
+    if outside && params.tiling.x < 0.5 {
+        color.a = 0.0;
+    }
+    var p = abs(in.clip_position);
+    var edge_size = edges.x * 2.0;
+    var s = size - edge_size;
+    if p.x < s.x || p.y < s.y {
+        return vec4<f32>(color.rgb, color.a*alpha);
+    }
+    var dist = distance(p, s);
+    if dist < edge_size {
+        return vec4<f32>(color.rgb, color.a*alpha);
+    }
+    var glow = 1.0 - ((dist - edge_size) / edges.y);
+    return vec4<f32>(color.rgb, color.a*alpha*glow);
+}
+
+
+
+fn vertex_position(vertex_index: u32) -> vec2<f32> {
+    // x: + + - - - +
+    // y: + - - - + +
+    return vec2<f32>((vec2(1u, 2u) + vertex_index) % vec2(6u) < vec2(3u))-0.5;
+}
+
+// Maps a pixel of the element onto the texture along one axis
+//
+// Borders keep their size on screen and shrink evenly if they do not fit,
+// the middle stretches. Without borders the texture is stretched.
+fn slice_axis(p: f32, len: f32, px_a: f32, px_b: f32, uv_a: f32, uv_b: f32) -> f32 {
+    var k = min(1.0, len / max(px_a + px_b, 0.0001));
+    var a = px_a * k;
+    var b = px_b * k;
+    if p < a {
+        return p / a * uv_a;
+    }
+    if p > len - b {
+        return 1.0 - (len - p) / b * uv_b;
+    }
+    return uv_a + (p - a) / max(len - a - b, 0.0001) * (1.0 - uv_a - uv_b);
+}
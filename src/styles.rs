@@ -620,6 +620,14 @@ pub struct RadialGradient {
     pub radius: ColorPoint,
 } */
 
+/// Rounding of the layout output, moved to `styles_proposition::Round`
+///
+/// `Round::Round` and `Round::Ceil` are gone, use `Round::Nearest` instead.
+/// The `position_round` and `scale_round` fields of the old `Transform` are
+/// replaced by `Styles::round`, `rotation_round` has no replacement
+#[deprecated(note = "use `styles_proposition::Round` and `Styles::round`")]
+pub type Round = styles_proposition::Round;
+
 #[derive(Debug, Clone, Copy, Default)]
 #[repr(C)]
 #[derive(bytemuck::Pod, bytemuck::Zeroable)]
@@ -765,13 +773,34 @@ impl From<Color> for [f32; 4] {
 }
 
 pub mod styles_proposition {
-    use std::sync::Arc;
+    use std::{collections::HashMap, sync::Arc};
 
-    use crate::{rotate_point, texture::Texture, ElementTransform, Point};
+    use crate::{
+        animation::Transitions,
+        rotate_point,
+        texture::{AtlasRegion, Fit, ImageAlign, NineSlice, Texture, Tiling, UvRect},
+        ElementKey, ElementTransform, Point,
+    };
 
     pub struct StyleComponent<S> {
         pub(crate) style: S,
         pub(crate) dirty: bool,
+        /// Whether the style changed since it was last passed on to the children
+        ///
+        /// Unlike `dirty` it is not set by animations
+        pub(crate) changed: bool,
+        /// Whether the style was set on the `Element` itself
+        ///
+        /// Inline styles take precedence over styles from classes
+        pub(crate) inline: bool,
+        /// Style resolved from classes or inherited from the parent
+        pub(crate) cascaded: Option<S>,
+        /// Style resolved from the state of the `Element`
+        ///
+        /// State styles take precedence over inline styles
+        pub(crate) state: Option<S>,
+        /// Style with references to the `Theme` replaced by their values
+        pub(crate) themed: Option<S>,
     }
 
     impl<T> StyleComponent<T> {
@@ -779,6 +808,24 @@ pub mod styles_proposition {
             Self {
                 style: c,
                 dirty: true,
+                changed: true,
+                inline: true,
+                cascaded: None,
+                state: None,
+                themed: None,
+            }
+        }
+
+        /// Creates a component holding a default value that classes can override
+        pub(crate) fn initial(c: T) -> Self {
+            Self {
+                style: c,
+                dirty: true,
+                changed: true,
+                inline: false,
+                cascaded: None,
+                state: None,
+                themed: None,
             }
         }
     }
@@ -787,6 +834,20 @@ pub mod styles_proposition {
         pub position: StyleComponent<Position>,
         pub width: StyleComponent<Values>,
         pub height: StyleComponent<Values>,
+        /// Width that the `Element` can not go below, `None` for no limit
+        pub min_width: StyleComponent<Option<Values>>,
+        /// Width that the `Element` can not go above, `None` for no limit
+        pub max_width: StyleComponent<Option<Values>>,
+        /// Height that the `Element` can not go below, `None` for no limit
+        pub min_height: StyleComponent<Option<Values>>,
+        /// Height that the `Element` can not go above, `None` for no limit
+        pub max_height: StyleComponent<Option<Values>>,
+        /// Shrinks the `Element` to keep the ratio of width to height
+        ///
+        /// Applied before the min and max constraints
+        pub aspect_ratio: StyleComponent<AspectRatio>,
+        /// Rounding of the computed position and size, `None` uses the rounding of the `Gui`
+        pub round: StyleComponent<Option<Round>>,
         pub rotation: StyleComponent<Rotation>,
         pub bg_color: StyleComponent<Colors>,
         pub margin: StyleComponent<Values>,
@@ -795,9 +856,27 @@ pub mod styles_proposition {
         pub text_color: StyleComponent<Colors>,
         pub text_size: StyleComponent<Values>,
         pub texture: StyleComponent<Option<Arc<Texture>>>,
+        /// Draws the texture without stretching its borders
+        pub nine_slice: StyleComponent<Option<NineSlice>>,
+        /// Size of the texture inside of the `Element`
+        pub fit: StyleComponent<Fit>,
+        /// Placement of the texture when it does not fill the `Element`
+        pub image_align: StyleComponent<ImageAlign>,
+        /// Part of the texture that is drawn
+        pub uv_rect: StyleComponent<UvRect>,
+        /// Fills the rest of the `Element` with copies of the texture
+        pub tiling: StyleComponent<Tiling>,
         pub edges_radius: StyleComponent<Values>,
         pub edges_smooth: StyleComponent<Values>,
+        /// Font family used for text, `None` uses the default font
+        pub font: StyleComponent<Option<String>>,
+        /// Animates changes of style properties, see `Gui::advance`
+        pub transitions: StyleComponent<Transitions>,
+        /// Style overrides applied while the `Element` is hovered, pressed, focused or disabled
+        pub states: StateStyles,
         pub visible: bool,
+        /// Disabled `Elements` do not receive any events and can not be selected
+        pub disabled: bool,
         pub selectable: bool,
         pub z_index: i32,
     }
@@ -805,48 +884,656 @@ pub mod styles_proposition {
     impl Default for Styles {
         fn default() -> Self {
             Self {
-                position: Position::new_c(),
-                width: StyleComponent::new(Values::Value(Value::Container(
+                position: StyleComponent::initial(Position::center()),
+                width: StyleComponent::initial(Values::Value(Value::Container(
                     RValue::Full,
                     Side::Width,
                 ))),
-                height: StyleComponent::new(Values::Value(Value::Container(
+                height: StyleComponent::initial(Values::Value(Value::Container(
                     RValue::Full,
                     Side::Height,
                 ))),
-                rotation: StyleComponent::new(Rotation::None),
-                bg_color: StyleComponent::new(Colors::Rgba(0.0, 0.0, 0.0, 0.0)),
-                margin: StyleComponent::new(Values::Value(Value::Zero)),
-                padding: StyleComponent::new(Values::Value(Value::Zero)),
-                text_color: StyleComponent::new(Colors::BLACK),
-                text_size: StyleComponent::new(Values::Value(Value::Pixel(50.0))),
-                texture: StyleComponent::new(None),
-                edges_radius: StyleComponent::new(Values::Value(Value::Zero)),
-                edges_smooth: StyleComponent::new(Values::Value(Value::Zero)),
-                alpha: StyleComponent::new(1.0),
+                min_width: StyleComponent::initial(None),
+                max_width: StyleComponent::initial(None),
+                min_height: StyleComponent::initial(None),
+                max_height: StyleComponent::initial(None),
+                aspect_ratio: StyleComponent::initial(AspectRatio::None),
+                round: StyleComponent::initial(None),
+                rotation: StyleComponent::initial(Rotation::None),
+                bg_color: StyleComponent::initial(Colors::Rgba(0.0, 0.0, 0.0, 0.0)),
+                margin: StyleComponent::initial(Values::Value(Value::Zero)),
+                padding: StyleComponent::initial(Values::Value(Value::Zero)),
+                text_color: StyleComponent::initial(Colors::BLACK),
+                text_size: StyleComponent::initial(Values::Value(Value::Pixel(50.0))),
+                texture: StyleComponent::initial(None),
+                nine_slice: StyleComponent::initial(None),
+                fit: StyleComponent::initial(Fit::Fill),
+                image_align: StyleComponent::initial(ImageAlign::CENTER),
+                uv_rect: StyleComponent::initial(UvRect::FULL),
+                tiling: StyleComponent::initial(Tiling::None),
+                edges_radius: StyleComponent::initial(Values::Value(Value::Zero)),
+                edges_smooth: StyleComponent::initial(Values::Value(Value::Zero)),
+                font: StyleComponent::initial(None),
+                transitions: StyleComponent::initial(Transitions::default()),
+                alpha: StyleComponent::initial(1.0),
+                states: StateStyles::default(),
                 visible: true,
+                disabled: false,
                 selectable: false,
                 z_index: 0,
             }
         }
     }
 
+    impl Styles {
+        /// Shows a region of a `TextureAtlas` or a frame of a `SpriteSheet`
+        pub fn set_region(&mut self, region: &AtlasRegion) {
+            self.texture.set(Some(region.texture.clone()));
+            self.uv_rect.set(region.uv);
+        }
+
+        /// Resolves styles that were not set inline from classes and the parent
+        ///
+        /// Classes later in the list take precedence. Text color, text size and font
+        /// are inherited from the parent when no class sets them.
+        pub(crate) fn cascade(&mut self, classes: &[&Styles], inherited: Option<&Inherited>) {
+            macro_rules! cascade {
+                ($($field: ident),*) => {$(
+                    let value = classes
+                        .iter()
+                        .rev()
+                        .find(|class| class.$field.inline)
+                        .map(|class| class.$field.style.clone());
+                    self.$field.cascade(value);
+                )*};
+            }
+            macro_rules! inherit {
+                ($($field: ident),*) => {$(
+                    let value = classes
+                        .iter()
+                        .rev()
+                        .find(|class| class.$field.inline)
+                        .map(|class| class.$field.style.clone())
+                        .or_else(|| inherited.map(|inherited| inherited.$field.clone()));
+                    self.$field.cascade(value);
+                )*};
+            }
+            cascade!(
+                position,
+                width,
+                height,
+                min_width,
+                max_width,
+                min_height,
+                max_height,
+                aspect_ratio,
+                round,
+                rotation,
+                bg_color,
+                margin,
+                padding,
+                alpha,
+                texture,
+                nine_slice,
+                fit,
+                image_align,
+                uv_rect,
+                tiling,
+                edges_radius,
+                edges_smooth,
+                transitions
+            );
+            inherit!(text_color, text_size, font);
+        }
+
+        /// Resolves style overrides for the current state of the `Element`
+        ///
+        /// Precedence from lowest: hover, focus, active, disabled.
+        /// State styles of the `Element` take precedence over those of its classes.
+        pub(crate) fn restate(&mut self, classes: &[&Styles], state: ElementState) {
+            let states = std::mem::take(&mut self.states);
+            let mut layers: Vec<&Styles> = Vec::new();
+            macro_rules! layer {
+                ($active: expr, $state: ident) => {
+                    if $active {
+                        for class in classes {
+                            if let Some(styles) = &class.states.$state {
+                                layers.push(styles);
+                            }
+                        }
+                        if let Some(styles) = &states.$state {
+                            layers.push(styles);
+                        }
+                    }
+                };
+            }
+            layer!(state.hovered, hover);
+            layer!(state.focused, focus);
+            layer!(state.pressed, active);
+            layer!(state.disabled, disabled);
+            macro_rules! restate {
+                ($($field: ident),*) => {$(
+                    let value = layers
+                        .iter()
+                        .rev()
+                        .find(|layer| layer.$field.inline)
+                        .map(|layer| layer.$field.style.clone());
+                    self.$field.restate(value);
+                )*};
+            }
+            restate!(
+                position,
+                width,
+                height,
+                min_width,
+                max_width,
+                min_height,
+                max_height,
+                aspect_ratio,
+                round,
+                rotation,
+                bg_color,
+                margin,
+                padding,
+                alpha,
+                text_color,
+                text_size,
+                texture,
+                nine_slice,
+                fit,
+                image_align,
+                uv_rect,
+                tiling,
+                edges_radius,
+                edges_smooth,
+                font,
+                transitions
+            );
+            self.states = states;
+        }
+
+        /// Resolves references to the `Theme`
+        ///
+        /// Pass `force` when the theme itself has changed
+        pub(crate) fn retheme(&mut self, theme: &Theme, force: bool) {
+            macro_rules! retheme {
+                ($($field: ident),*) => {$(
+                    self.$field.retheme(theme, force);
+                )*};
+            }
+            retheme!(
+                position,
+                width,
+                height,
+                min_width,
+                max_width,
+                min_height,
+                max_height,
+                aspect_ratio,
+                round,
+                rotation,
+                bg_color,
+                margin,
+                padding,
+                alpha,
+                text_color,
+                text_size,
+                texture,
+                nine_slice,
+                fit,
+                image_align,
+                uv_rect,
+                tiling,
+                edges_radius,
+                edges_smooth,
+                font,
+                transitions
+            );
+        }
+
+        /// Styles that are passed down to children
+        pub(crate) fn inherited(&self) -> Inherited {
+            Inherited {
+                text_color: self.text_color.get().clone(),
+                text_size: self.text_size.get().clone(),
+                font: self.font.get().clone(),
+            }
+        }
+
+        /// Returns true if the inheritable styles changed since the last call
+        pub(crate) fn take_inherited_changed(&mut self) -> bool {
+            let changed = self.text_color.changed || self.text_size.changed || self.font.changed;
+            self.text_color.changed = false;
+            self.text_size.changed = false;
+            self.font.changed = false;
+            changed
+        }
+    }
+
+    /// Palette of named colors and values that styles can reference
+    ///
+    /// Reference theme entries using `Colors::Theme` and `Value::Theme`.
+    /// Switching the theme of a `Gui` restyles all `Elements`.
+    #[derive(Debug, Clone, Default)]
+    pub struct Theme {
+        colors: HashMap<String, Colors>,
+        values: HashMap<String, Values>,
+    }
+
+    impl Theme {
+        /// Creates an empty `Theme`
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Light palette with common color roles, font sizes, spacing and corner radii
+        pub fn light() -> Self {
+            Self::new()
+                .with_color("background", Colors::Rgb(0.96, 0.96, 0.97))
+                .with_color("surface", Colors::WHITE)
+                .with_color("accent", Colors::Rgb(0.2, 0.45, 0.9))
+                .with_color("text", Colors::Rgb(0.1, 0.1, 0.12))
+                .with_color("text_muted", Colors::Rgb(0.4, 0.4, 0.45))
+                .with_color("border", Colors::Rgb(0.8, 0.8, 0.83))
+                .with_color("error", Colors::Rgb(0.85, 0.2, 0.2))
+                .with_common_values()
+        }
+
+        /// Dark palette with common color roles, font sizes, spacing and corner radii
+        pub fn dark() -> Self {
+            Self::new()
+                .with_color("background", Colors::Rgb(0.08, 0.08, 0.1))
+                .with_color("surface", Colors::Rgb(0.15, 0.15, 0.18))
+                .with_color("accent", Colors::Rgb(0.35, 0.6, 1.0))
+                .with_color("text", Colors::Rgb(0.92, 0.92, 0.94))
+                .with_color("text_muted", Colors::Rgb(0.6, 0.6, 0.65))
+                .with_color("border", Colors::Rgb(0.3, 0.3, 0.35))
+                .with_color("error", Colors::Rgb(1.0, 0.4, 0.4))
+                .with_common_values()
+        }
+
+        fn with_common_values(self) -> Self {
+            self.with_value("font_size", Values::logical(20.0))
+                .with_value("font_size_small", Values::logical(14.0))
+                .with_value("font_size_large", Values::logical(32.0))
+                .with_value("spacing", Values::logical(8.0))
+                .with_value("radius", Values::logical(6.0))
+        }
+
+        pub fn with_color(mut self, name: &str, color: Colors) -> Self {
+            self.set_color(name, color);
+            self
+        }
+
+        pub fn with_value(mut self, name: &str, value: Values) -> Self {
+            self.set_value(name, value);
+            self
+        }
+
+        pub fn set_color(&mut self, name: &str, color: Colors) {
+            self.colors.insert(name.to_string(), color);
+        }
+
+        pub fn set_value(&mut self, name: &str, value: Values) {
+            self.values.insert(name.to_string(), value);
+        }
+
+        pub fn color(&self, name: &str) -> Option<&Colors> {
+            self.colors.get(name)
+        }
+
+        pub fn value(&self, name: &str) -> Option<&Values> {
+            self.values.get(name)
+        }
+    }
+
+    /// Style that can reference entries of a `Theme`
+    pub trait Themed: Clone {
+        /// Returns the style with theme references replaced
+        ///
+        /// Returns `None` if the style does not reference the theme
+        fn resolve(&self, _theme: &Theme) -> Option<Self> {
+            None
+        }
+    }
+
+    impl Themed for Colors {
+        fn resolve(&self, theme: &Theme) -> Option<Self> {
+            match self {
+                Colors::Theme(name, alpha) => theme.color(name).map(|color| match alpha {
+                    Some(alpha) => color.with_alpha(*alpha),
+                    None => color.clone(),
+                }),
+                _ => None,
+            }
+        }
+    }
+
+    impl Themed for Values {
+        fn resolve(&self, theme: &Theme) -> Option<Self> {
+            match self {
+                Values::Value(Value::Theme(name)) => theme.value(name).cloned(),
+                Values::Value(_) => None,
+                Values::Expr(expr) => {
+                    match (expr.left.resolve(theme), expr.right.resolve(theme)) {
+                        (None, None) => None,
+                        (left, right) => Some(Values::Expr(Box::new(Expression {
+                            left: left.unwrap_or_else(|| expr.left.clone()),
+                            right: right.unwrap_or_else(|| expr.right.clone()),
+                            op: expr.op.clone(),
+                        }))),
+                    }
+                }
+                Values::Function(fun) => fun.value.resolve(theme).map(|value| {
+                    Values::Function(Box::new(Function {
+                        value,
+                        fun: fun.fun.clone(),
+                    }))
+                }),
+            }
+        }
+    }
+
+    impl Themed for Position {
+        fn resolve(&self, theme: &Theme) -> Option<Self> {
+            let x = self.offset.0.as_ref().and_then(|x| x.resolve(theme));
+            let y = self.offset.1.as_ref().and_then(|y| y.resolve(theme));
+            if x.is_none() && y.is_none() {
+                return None;
+            }
+            Some(Self {
+                offset: (x.or_else(|| self.offset.0.clone()), y.or_else(|| self.offset.1.clone())),
+                ..self.clone()
+            })
+        }
+    }
+
+    impl Themed for Option<Values> {
+        fn resolve(&self, theme: &Theme) -> Option<Self> {
+            self.as_ref().and_then(|values| values.resolve(theme)).map(Some)
+        }
+    }
+
+    impl Themed for f32 {}
+    impl Themed for AspectRatio {}
+    impl Themed for Option<Round> {}
+    impl Themed for Rotation {}
+    impl Themed for Transitions {}
+    impl Themed for Option<String> {}
+    impl Themed for Option<Arc<Texture>> {}
+    impl Themed for Option<NineSlice> {}
+    impl Themed for Fit {}
+    impl Themed for ImageAlign {}
+    impl Themed for UvRect {}
+    impl Themed for Tiling {}
+
+    /// Style overrides applied while an `Element` is in a certain state
+    ///
+    /// Only the style components that were explicitly set are applied.
+    /// Overrides are resolved when the state of the `Element` or its classes change
+    #[derive(Default)]
+    pub struct StateStyles {
+        /// Applied while the mouse is over the `Element`
+        pub hover: Option<Box<Styles>>,
+        /// Applied while the `Element` is selected
+        pub focus: Option<Box<Styles>>,
+        /// Applied while a mouse button is held down on the `Element`
+        pub active: Option<Box<Styles>>,
+        /// Applied while the `Element` is disabled
+        pub disabled: Option<Box<Styles>>,
+    }
+
+    impl StateStyles {
+        pub fn hover_mut(&mut self) -> &mut Styles {
+            self.hover.get_or_insert_with(Default::default)
+        }
+
+        pub fn focus_mut(&mut self) -> &mut Styles {
+            self.focus.get_or_insert_with(Default::default)
+        }
+
+        pub fn active_mut(&mut self) -> &mut Styles {
+            self.active.get_or_insert_with(Default::default)
+        }
+
+        pub fn disabled_mut(&mut self) -> &mut Styles {
+            self.disabled.get_or_insert_with(Default::default)
+        }
+    }
+
+    /// Current state of an `Element`
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub struct ElementState {
+        /// Mouse is over the `Element`
+        pub hovered: bool,
+        /// `Element` is selected
+        pub focused: bool,
+        /// A mouse button is held down on the `Element`
+        pub pressed: bool,
+        /// `Element` is disabled
+        pub disabled: bool,
+    }
+
+    impl ElementState {
+        /// Returns true if any state is active
+        pub fn any(&self) -> bool {
+            self.hovered || self.focused || self.pressed || self.disabled
+        }
+    }
+
+    /// Inheritable styles of a parent `Element`
+    pub(crate) struct Inherited {
+        text_color: Colors,
+        text_size: Values,
+        font: Option<String>,
+    }
+
+    /// Collection of named style classes shared between `Elements`
+    ///
+    /// Only the style components that were explicitly set on a class
+    /// are applied to the `Elements` using it.
+    ///
+    /// Styles can also be assigned to `Elements` by their label.
+    /// Label styles take precedence over classes.
+    #[derive(Default)]
+    pub struct StyleSheet {
+        classes: HashMap<String, Styles>,
+        labels: HashMap<String, Styles>,
+        pub(crate) dirty: bool,
+    }
+
+    impl StyleSheet {
+        /// Creates an empty `StyleSheet`
+        pub fn new() -> Self {
+            Self {
+                classes: HashMap::new(),
+                labels: HashMap::new(),
+                dirty: false,
+            }
+        }
+
+        /// Registers or replaces a class
+        pub fn set_class(&mut self, name: &str, styles: Styles) {
+            self.dirty = true;
+            self.classes.insert(name.to_string(), styles);
+        }
+
+        pub fn get_class(&self, name: &str) -> Option<&Styles> {
+            self.classes.get(name)
+        }
+
+        /// Returns a class for modification
+        ///
+        /// All `Elements` will be restyled on the next update
+        pub fn class_mut(&mut self, name: &str) -> Option<&mut Styles> {
+            self.dirty = true;
+            self.classes.get_mut(name)
+        }
+
+        pub fn remove_class(&mut self, name: &str) -> Option<Styles> {
+            self.dirty = true;
+            self.classes.remove(name)
+        }
+
+        /// Registers or replaces styles of `Elements` with the given label
+        pub fn set_label(&mut self, label: &str, styles: Styles) {
+            self.dirty = true;
+            self.labels.insert(label.to_string(), styles);
+        }
+
+        pub fn get_label(&self, label: &str) -> Option<&Styles> {
+            self.labels.get(label)
+        }
+
+        /// Returns styles of a label for modification
+        ///
+        /// All `Elements` will be restyled on the next update
+        pub fn label_mut(&mut self, label: &str) -> Option<&mut Styles> {
+            self.dirty = true;
+            self.labels.get_mut(label)
+        }
+
+        pub fn remove_label(&mut self, label: &str) -> Option<Styles> {
+            self.dirty = true;
+            self.labels.remove(label)
+        }
+
+        pub(crate) fn class_entry(&mut self, name: &str) -> &mut Styles {
+            self.dirty = true;
+            self.classes.entry(name.to_string()).or_default()
+        }
+
+        pub(crate) fn label_entry(&mut self, label: &str) -> &mut Styles {
+            self.dirty = true;
+            self.labels.entry(label.to_string()).or_default()
+        }
+    }
+
+    /// Places an `Element` inside of its parent
+    ///
+    /// The `pivot` point of the `Element` is placed at the `value` anchor of the parent,
+    /// then the offset is added
+    ///
+    /// ```ignore
+    /// // top left corner of the element follows the cursor
+    /// let position = Position::top_left()
+    ///     .in_view_port()
+    ///     .with_pivot(PositionValues::TopLeft)
+    ///     .with_offset(mouse.x, mouse.y);
+    /// ```
+    #[derive(Debug, Clone, PartialEq)]
     pub struct Position {
         parent: Parent,
         value: PositionValues,
+        pivot: PositionValues,
         offset: (Option<Values>, Option<Values>),
     }
 
+    macro_rules! anchors {
+        ($($fn: ident, $value: ident;)*) => {
+            $(
+                #[doc = concat!("Shortcut for `Position::new(PositionValues::", stringify!($value), ")`")]
+                pub fn $fn() -> Self {
+                    Self::new(PositionValues::$value)
+                }
+            )*
+        };
+    }
+
     impl Position {
-        pub fn new_c() -> StyleComponent<Self> {
-            StyleComponent::new(Self {
+        /// Anchors the `Element` to its container
+        pub fn new(value: PositionValues) -> Self {
+            Self {
                 parent: Parent::Container,
-                value: PositionValues::Center,
+                value,
+                pivot: PositionValues::Center,
                 offset: (None, None),
-            })
+            }
+        }
+
+        anchors! {
+            top, Top;
+            top_left, TopLeft;
+            top_right, TopRight;
+            center, Center;
+            center_left, CenterLeft;
+            center_right, CenterRight;
+            bottom, Bottom;
+            bottom_left, BottomLeft;
+            bottom_right, BottomRight;
+        }
+
+        /// Anchors to a fraction of the parent, `(0.0, 0.0)` is the top left corner
+        pub fn fraction(x: f32, y: f32) -> Self {
+            Self::new(PositionValues::Fraction(x, y))
+        }
+
+        pub fn with_parent(mut self, parent: Parent) -> Self {
+            self.parent = parent;
+            self
+        }
+
+        /// Shortcut for `with_parent(Parent::ViewPort)`
+        pub fn in_view_port(self) -> Self {
+            self.with_parent(Parent::ViewPort)
+        }
+
+        /// Shortcut for `with_parent(Parent::Element(key))`
+        pub fn relative_to(self, key: ElementKey) -> Self {
+            self.with_parent(Parent::Element(key))
+        }
+
+        /// Point of the `Element` that is placed at the anchor
+        ///
+        /// Defaults to `PositionValues::Center`
+        pub fn with_pivot(mut self, pivot: PositionValues) -> Self {
+            self.pivot = pivot;
+            self
+        }
+
+        pub fn with_offset(mut self, x: impl Into<Values>, y: impl Into<Values>) -> Self {
+            self.offset = (Some(x.into()), Some(y.into()));
+            self
+        }
+
+        pub fn with_offset_x(mut self, x: impl Into<Values>) -> Self {
+            self.offset.0 = Some(x.into());
+            self
+        }
+
+        pub fn with_offset_y(mut self, y: impl Into<Values>) -> Self {
+            self.offset.1 = Some(y.into());
+            self
+        }
+
+        pub fn parent(&self) -> Parent {
+            self.parent
+        }
+
+        pub fn value(&self) -> PositionValues {
+            self.value
+        }
+
+        pub fn pivot(&self) -> PositionValues {
+            self.pivot
+        }
+
+        pub fn offset(&self) -> (Option<&Values>, Option<&Values>) {
+            (self.offset.0.as_ref(), self.offset.1.as_ref())
+        }
+
+        pub fn new_c() -> StyleComponent<Self> {
+            StyleComponent::new(Self::center())
+        }
+    }
+
+    impl From<PositionValues> for Position {
+        fn from(value: PositionValues) -> Self {
+            Self::new(value)
         }
     }
 
+    #[derive(Debug, Clone, PartialEq, Copy)]
     pub enum PositionValues {
         Top,
         TopLeft,
@@ -857,18 +1544,52 @@ pub mod styles_proposition {
         Bottom,
         BottomLeft,
         BottomRight,
+        /// Fraction of the width and height, `(0.0, 0.0)` is the top left corner
+        /// and `(1.0, 1.0)` the bottom right corner
+        Fraction(f32, f32),
     }
 
+    impl PositionValues {
+        /// Returns the anchor as a fraction of the width and height
+        pub fn fraction(&self) -> (f32, f32) {
+            match self {
+                PositionValues::Top => (0.5, 0.0),
+                PositionValues::TopLeft => (0.0, 0.0),
+                PositionValues::TopRight => (1.0, 0.0),
+                PositionValues::Center => (0.5, 0.5),
+                PositionValues::CenterLeft => (0.0, 0.5),
+                PositionValues::CenterRight => (1.0, 0.5),
+                PositionValues::Bottom => (0.5, 1.0),
+                PositionValues::BottomLeft => (0.0, 1.0),
+                PositionValues::BottomRight => (1.0, 1.0),
+                PositionValues::Fraction(x, y) => (*x, *y),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Copy)]
     pub enum Parent {
+        /// Position relative to the whole window
         ViewPort,
+        /// Position relative to the space given by the parent `Element`
         Container,
+        /// Position relative to another `Element`
+        ///
+        /// Only applies to overlays and roots of layers, see `Element::overlays`.
+        /// Elsewhere it behaves like `Parent::Container`
+        Element(ElementKey),
     }
 
+    #[derive(Debug, Clone, PartialEq)]
     pub enum Colors {
         Rgb(f32, f32, f32),
         Rgba(f32, f32, f32, f32),
         Hsl(f32, f32, f32),
         Cmyk(f32, f32, f32, f32),
+        /// Named color of the current `Theme` and an optional alpha replacing its own
+        ///
+        /// Resolves to transparent if the theme does not contain the color
+        Theme(String, Option<f32>),
     }
 
     impl Colors {
@@ -891,6 +1612,7 @@ pub mod styles_proposition {
                 Colors::Rgba(r, g, b, a) => (*r, *g, *b, *a),
                 Colors::Hsl(h, s, l) => Self::hsl_to_rgba(*h, *s, *l),
                 Colors::Cmyk(c, m, y, k) => Self::cmyk_to_rgba(*c, *m, *y, *k),
+                Colors::Theme(..) => (0.0, 0.0, 0.0, 0.0),
             }
         }
 
@@ -916,6 +1638,9 @@ pub mod styles_proposition {
             (r, g, b, 1.0)
         }
 
+        /// Returns the color with alpha set to `a`
+        ///
+        /// For `Theme` colors the alpha is applied when the color is resolved
         pub fn with_alpha(&self, a: f32) -> Self {
             match *self {
                 Colors::Rgb(r, g, b) => Colors::Rgba(r, g, b, a),
@@ -928,11 +1653,108 @@ pub mod styles_proposition {
                     let (r, g, b, _) = Self::cmyk_to_rgba(c, m, y, k);
                     Colors::Rgba(r, g, b, a)
                 }
+                Colors::Theme(ref name, _) => Colors::Theme(name.clone(), Some(a)),
             }
         }
     }
 
-    #[derive(Debug, Default, Clone, Copy)]
+    /// Ratio of width to height
+    #[derive(Debug, Default, Clone, PartialEq, Copy)]
+    pub enum AspectRatio {
+        #[default]
+        None,
+        /// Width divided by height
+        Ratio(f32),
+        /// Ratio of the texture, ignored if the `Element` has no texture
+        Image,
+    }
+
+    impl AspectRatio {
+        /// Shrinks `width` or `height` to fit the ratio
+        pub fn fit(&self, width: f32, height: f32, image: Option<(f32, f32)>) -> (f32, f32) {
+            let ratio = match (self, image) {
+                (AspectRatio::Ratio(ratio), _) => *ratio,
+                (AspectRatio::Image, Some((w, h))) if h > 0.0 => w / h,
+                _ => return (width, height),
+            };
+            if ratio <= 0.0 || height <= 0.0 {
+                return (width, height);
+            }
+            match width / height > ratio {
+                true => (height * ratio, height),
+                false => (width, width / ratio),
+            }
+        }
+    }
+
+    /// Rounding of the layout output
+    ///
+    /// Fractional positions and sizes blur thin lines and text.
+    /// Every mode snaps the edges of the `Element`, the center and the size follow
+    /// from the snapped edges, so adjacent `Elements` never overlap or leave gaps
+    #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+    pub enum Round {
+        #[default]
+        None,
+        /// Floors the edges to logical pixels
+        Floor,
+        /// Rounds the edges to the nearest logical pixel
+        ///
+        /// Keeps the layout aligned to the same grid at every scale factor
+        Nearest,
+        /// Rounds the edges to the nearest physical pixel of the device
+        ///
+        /// Gives the sharpest lines, but edges land on different logical positions
+        /// depending on the scale factor.
+        /// Rotated `Elements` are not axis aligned, only their center and size are rounded
+        DevicePixel,
+    }
+
+    impl Round {
+        /// Snaps the `transform`, which is in physical pixels
+        pub(crate) fn apply(
+            &self,
+            transform: ElementTransform,
+            scale_factor: f32,
+        ) -> ElementTransform {
+            let ElementTransform {
+                position,
+                scale,
+                rotation,
+            } = transform;
+            // size of a pixel of the grid in physical pixels
+            let (op, grid): (fn(f32) -> f32, f32) = match self {
+                Round::None => return transform,
+                Round::Floor => (f32::floor, scale_factor),
+                Round::Nearest => (f32::round, scale_factor),
+                Round::DevicePixel => (f32::round, 1.0),
+            };
+            let snap = |value: f32| op(value / grid) * grid;
+            let (position, scale) = match self {
+                Round::DevicePixel if rotation != 0.0 => (
+                    Point::new(position.x.round(), position.y.round()),
+                    Point::new(scale.x.round(), scale.y.round()),
+                ),
+                _ => {
+                    let left = snap(position.x - scale.x / 2.0);
+                    let top = snap(position.y - scale.y / 2.0);
+                    let right = snap(position.x + scale.x / 2.0);
+                    let bottom = snap(position.y + scale.y / 2.0);
+                    (
+                        Point::new((left + right) / 2.0, (top + bottom) / 2.0),
+                        Point::new(right - left, bottom - top),
+                    )
+                }
+            };
+            ElementTransform {
+                position,
+                scale,
+                rotation,
+            }
+        }
+    }
+
+    #[derive(Debug, Default, Clone, PartialEq, Copy)]
     pub enum Rotation {
         Deg(f32),
         Rad(f32),
@@ -957,7 +1779,7 @@ pub mod styles_proposition {
     }
 
     /// Returns value
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum Values {
         /// Perform an operation
         Expr(Box<Expression>),
@@ -968,7 +1790,7 @@ pub mod styles_proposition {
     }
 
     /// Performs an operation
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     pub struct Expression {
         /// Left side of operation
         left: Values,
@@ -979,14 +1801,145 @@ pub mod styles_proposition {
     }
 
     /// A function
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     pub struct Function {
         value: Values,
         fun: Functions,
     }
 
+    impl Expression {
+        pub fn new(left: Values, op: Op, right: Values) -> Self {
+            Self { left, right, op }
+        }
+        pub fn left(&self) -> &Values {
+            &self.left
+        }
+        pub fn right(&self) -> &Values {
+            &self.right
+        }
+        pub fn op(&self) -> &Op {
+            &self.op
+        }
+    }
+
+    impl Function {
+        pub fn new(fun: Functions, value: Values) -> Self {
+            Self { value, fun }
+        }
+        pub fn value(&self) -> &Values {
+            &self.value
+        }
+        pub fn fun(&self) -> &Functions {
+            &self.fun
+        }
+    }
+
+    impl Values {
+        /// Shortcut for `Values::Value(Value::Pixel(px))`
+        pub fn px(px: f32) -> Self {
+            Values::Value(Value::Pixel(px))
+        }
+        /// Shortcut for `Values::Value(Value::Logical(px))`
+        pub fn logical(px: f32) -> Self {
+            Values::Value(Value::Logical(px))
+        }
+        /// Percentage of a side of the container
+        pub fn container(percent: f32, side: Side) -> Self {
+            Values::Value(Value::Container(RValue::Percent(percent), side))
+        }
+        /// Percentage of a side of the view port
+        pub fn view_port(percent: f32, side: Side) -> Self {
+            Values::Value(Value::ViewPort(RValue::Percent(percent), side))
+        }
+        /// Percentage of a side of the image
+        pub fn image(percent: f32, side: Side) -> Self {
+            Values::Value(Value::Image(RValue::Percent(percent), side))
+        }
+        /// Named value of the current `Theme`
+        pub fn theme(name: impl Into<String>) -> Self {
+            Values::Value(Value::Theme(name.into()))
+        }
+        pub fn expr(left: Values, op: Op, right: Values) -> Self {
+            Values::Expr(Box::new(Expression::new(left, op, right)))
+        }
+        pub fn function(fun: Functions, value: Values) -> Self {
+            Values::Function(Box::new(Function::new(fun, value)))
+        }
+        pub fn min(self, other: impl Into<Values>) -> Self {
+            Self::expr(self, Op::Min, other.into())
+        }
+        pub fn max(self, other: impl Into<Values>) -> Self {
+            Self::expr(self, Op::Max, other.into())
+        }
+        /// Restricts the value between `min` and `max`
+        pub fn clamp(self, min: impl Into<Values>, max: impl Into<Values>) -> Self {
+            self.max(min).min(max)
+        }
+        pub fn pow(self, other: impl Into<Values>) -> Self {
+            Self::expr(self, Op::Pow, other.into())
+        }
+        pub fn round(self) -> Self {
+            Self::function(Functions::Round, self)
+        }
+        pub fn floor(self) -> Self {
+            Self::function(Functions::Floor, self)
+        }
+        pub fn ceil(self) -> Self {
+            Self::function(Functions::Ceil, self)
+        }
+        pub fn sqrt(self) -> Self {
+            Self::function(Functions::Sqrt, self)
+        }
+        pub fn abs(self) -> Self {
+            Self::function(Functions::Abs, self)
+        }
+    }
+
+    impl From<Value> for Values {
+        fn from(value: Value) -> Self {
+            Values::Value(value)
+        }
+    }
+
+    /// Pixels
+    impl From<f32> for Values {
+        fn from(px: f32) -> Self {
+            Values::px(px)
+        }
+    }
+
+    macro_rules! values_op {
+        ($($trait: ident, $fn: ident, $op: ident;)*) => {
+            $(
+                impl<T: Into<Values>> std::ops::$trait<T> for Values {
+                    type Output = Values;
+
+                    fn $fn(self, rhs: T) -> Values {
+                        Values::expr(self, Op::$op, rhs.into())
+                    }
+                }
+            )*
+        };
+    }
+
+    values_op! {
+        Add, add, Add;
+        Sub, sub, Sub;
+        Mul, mul, Mul;
+        Div, div, Div;
+        Rem, rem, Mod;
+    }
+
+    impl std::ops::Neg for Values {
+        type Output = Values;
+
+        fn neg(self) -> Values {
+            Values::expr(Values::Value(Value::Zero), Op::Sub, self)
+        }
+    }
+
     /// Choose measured unit
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum Value {
         /// This is the space that is given to the element
         Container(RValue, Side),
@@ -1004,14 +1957,20 @@ pub mod styles_proposition {
         /// 3. text - text
         /// 4. ___ - [1px, 1px]
         Content(RValue, Side),
-        /// Size in pixels
+        /// Size in physical pixels
         Pixel(f32),
+        /// Size in logical pixels, multiplied by the scale factor of the `Gui`
+        Logical(f32),
         /// Shortcut for `Value::Pixel(0.0)`
         Zero,
+        /// Named value of the current `Theme`
+        ///
+        /// Resolves to zero if the theme does not contain the value
+        Theme(String),
     }
 
     /// Returns size of a specified side/equation of the measured unit
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum Side {
         /// Returns width of the measured unit
         Width,
@@ -1033,7 +1992,7 @@ pub mod styles_proposition {
     }
 
     /// Performs operation on size
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum RValue {
         /// Returns a percentage of size `(size / 100) * Percent`
         Percent(f32),
@@ -1045,7 +2004,7 @@ pub mod styles_proposition {
         Full,
     }
 
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum Op {
         Add,
         Sub,
@@ -1057,7 +2016,7 @@ pub mod styles_proposition {
         Pow,
     }
 
-    #[derive(Debug, Clone)]
+    #[derive(Debug, Clone, PartialEq)]
     pub enum Functions {
         Round,
         Floor,
@@ -1068,62 +2027,137 @@ pub mod styles_proposition {
 
     impl<S> StyleComponent<S> {
         pub fn get(&self) -> &S {
-            &self.style
+            match &self.themed {
+                Some(style) => style,
+                None => self.layered(),
+            }
         }
-        pub fn get_mut(&mut self) -> &mut S {
-            self.dirty = true;
-            &mut self.style
+        /// Returns the style before `Theme` references are resolved
+        fn layered(&self) -> &S {
+            match &self.state {
+                Some(style) => style,
+                None => self.unstated(),
+            }
+        }
+        /// Returns the style without the overrides of the current state
+        fn unstated(&self) -> &S {
+            match &self.cascaded {
+                Some(style) if !self.inline => style,
+                _ => &self.style,
+            }
         }
         pub fn set(&mut self, style: S) {
             self.dirty = true;
+            self.changed = true;
+            self.inline = true;
             self.style = style;
         }
+        /// Removes the inline style so that classes and inheritance apply again
+        pub fn unset(&mut self) {
+            self.dirty = true;
+            self.changed = true;
+            self.inline = false;
+        }
+        /// Returns true if the style was set on the `Element` itself
+        pub fn is_inline(&self) -> bool {
+            self.inline
+        }
+    }
+
+    impl<S: PartialEq> StyleComponent<S> {
+        /// Replaces the style resolved from classes and the parent, marks the component
+        /// dirty only if the resulting style differs
+        pub(crate) fn cascade(&mut self, style: Option<S>) {
+            if self.cascaded == style {
+                return;
+            }
+            self.cascaded = style;
+            if !self.inline {
+                self.dirty = true;
+                self.changed = true;
+            }
+        }
+
+        /// Replaces the override of the current state, marks the component dirty
+        /// only if the resulting style differs
+        pub(crate) fn restate(&mut self, style: Option<S>) {
+            let changed = match (&self.state, &style) {
+                (Some(old), Some(new)) => old != new,
+                (Some(style), None) | (None, Some(style)) => style != self.unstated(),
+                (None, None) => false,
+            };
+            if changed {
+                self.dirty = true;
+                self.changed = true;
+            }
+            self.state = style;
+        }
+    }
+
+    impl<S: Themed> StyleComponent<S> {
+        /// Resolves references to the `Theme`
+        ///
+        /// Only dirty components are resolved unless `force` is true
+        pub(crate) fn retheme(&mut self, theme: &Theme, force: bool) {
+            if !(self.dirty || force) {
+                return;
+            }
+            let themed = self.layered().resolve(theme);
+            if self.themed.is_some() || themed.is_some() {
+                self.dirty = true;
+                self.changed = true;
+            }
+            self.themed = themed;
+        }
+    }
+
+    impl<S: Clone> StyleComponent<S> {
+        pub fn get_mut(&mut self) -> &mut S {
+            if !self.inline {
+                if let Some(style) = self.cascaded.take() {
+                    self.style = style;
+                }
+            }
+            self.dirty = true;
+            self.changed = true;
+            self.inline = true;
+            &mut self.style
+        }
     }
 
     impl Position {
-        pub fn calc(&self, container: &Container, view_port: &ViewPort) -> Point {
+        /// Returns the center of an `Element` of the given `size`
+        pub fn calc(&self, container: &Container, view_port: &ViewPort, size: Point) -> Point {
             let cont = match self.parent {
-                Parent::Container => *container,
+                Parent::Container | Parent::Element(_) => *container,
                 Parent::ViewPort => Container {
-                    image: None,
-                    position: Point::new(view_port.0 / 2.0, view_port.1 / 2.0),
+                    image: container.image,
+                    position: Point::new(view_port.width / 2.0, view_port.height / 2.0),
                     rotation: 0.0,
-                    size: Point::new(view_port.0, view_port.1),
+                    size: Point::new(view_port.width, view_port.height),
                 },
             };
             let offset_x = self
                 .offset
                 .0
                 .as_ref()
-                .map(|v| v.calc(container, view_port))
+                .map(|v| v.calc(&cont, view_port))
                 .unwrap_or(0.0);
             let offset_y = self
                 .offset
                 .1
                 .as_ref()
-                .map(|v| v.calc(container, view_port))
+                .map(|v| v.calc(&cont, view_port))
                 .unwrap_or(0.0);
-            macro_rules! corner {
-                ($left: expr, $right: expr) => {{
-                    let point = Point::new(
-                        cont.position.x + $left + offset_x,
-                        cont.position.y + $right + offset_y,
-                    );
-                    let point = rotate_point(point, cont.position, -cont.rotation);
-                    point
-                }};
-            }
-            match &self.value {
-                PositionValues::Top => corner!(-cont.position.x, -cont.position.y),
-                PositionValues::TopLeft => corner!(0.0, -cont.position.y),
-                PositionValues::TopRight => corner!(cont.position.x, -cont.position.y),
-                PositionValues::Center => corner!(0.0, 0.0),
-                PositionValues::CenterLeft => corner!(-cont.position.x, 0.0),
-                PositionValues::CenterRight => corner!(cont.position.x, 0.0),
-                PositionValues::Bottom => corner!(-cont.position.x, cont.position.y),
-                PositionValues::BottomLeft => corner!(0.0, cont.position.y),
-                PositionValues::BottomRight => corner!(cont.position.x, cont.position.y),
-            }
+            let (anchor_x, anchor_y) = self.value.fraction();
+            let (pivot_x, pivot_y) = self.pivot.fraction();
+            let point = Point::new(
+                cont.position.x + (anchor_x - 0.5) * cont.size.x - (pivot_x - 0.5) * size.x
+                    + offset_x,
+                cont.position.y + (anchor_y - 0.5) * cont.size.y - (pivot_y - 0.5) * size.y
+                    + offset_y,
+            );
+            rotate_point(point, cont.position, -cont.rotation)
         }
     }
 
@@ -1179,7 +2213,7 @@ pub mod styles_proposition {
                     r_value.calc(side.get_size(contaner.size.x, contaner.size.y))
                 }
                 Value::ViewPort(r_value, side) => {
-                    r_value.calc(side.get_size(view_port.0, view_port.1))
+                    r_value.calc(side.get_size(view_port.width, view_port.height))
                 }
                 Value::Image(r_value, side) => match &contaner.image {
                     Some(img) => r_value.calc(side.get_size(img.size.x, img.size.y)),
@@ -1188,7 +2222,9 @@ pub mod styles_proposition {
                 Value::Text(r_value, side) => todo!("Ouch thats gonna take a while"),
                 Value::Content(r_value, side) => todo!("Ouch thats gonna take a while"),
                 Value::Pixel(num) => *num,
+                Value::Logical(num) => *num * view_port.scale_factor,
                 Value::Zero => 0.0,
+                Value::Theme(_) => 0.0,
             }
         }
     }
@@ -1253,12 +2289,50 @@ pub mod styles_proposition {
         }
     }
 
+    /// Size of the window in physical pixels and its scale factor
+    ///
+    /// `Value::ViewPort` measures the size, `Value::Logical` uses the scale factor
     #[derive(Debug, Clone, Copy)]
-    pub struct ViewPort(pub f32, pub f32);
+    pub struct ViewPort {
+        pub width: f32,
+        pub height: f32,
+        /// Ratio of physical to logical pixels, used by `Value::Logical`
+        pub scale_factor: f32,
+    }
+
+    impl ViewPort {
+        /// Creates a `ViewPort` with the scale factor of `1.0`
+        pub fn new(width: f32, height: f32) -> Self {
+            Self {
+                width,
+                height,
+                scale_factor: 1.0,
+            }
+        }
+
+        pub fn with_scale_factor(mut self, scale_factor: f32) -> Self {
+            self.scale_factor = scale_factor;
+            self
+        }
+    }
 
     #[derive(Debug, Clone, Copy)]
     pub struct Rectangle {
         pub position: Point,
         pub size: Point,
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn theme_color_alpha_is_applied_when_resolved() {
+            let theme = Theme::new().with_color("accent", Colors::Rgb(1.0, 0.0, 0.0));
+            let color = Colors::Theme("accent".to_string(), None).with_alpha(0.5);
+            assert_eq!(color.resolve(&theme), Some(Colors::Rgba(1.0, 0.0, 0.0, 0.5)));
+            let color = Colors::Theme("accent".to_string(), None);
+            assert_eq!(color.resolve(&theme), Some(Colors::Rgb(1.0, 0.0, 0.0)));
+        }
+    }
 }
@@ -1,8 +1,13 @@
 //! Minimalistic module for textures
 
 
+use std::{collections::HashMap, path::Path, sync::Arc};
+
 use image::GenericImageView;
 
+use crate::{Error, Result};
+
+
 #[derive(Debug)]
 pub struct Texture {
     pub texture: wgpu::Texture,
@@ -11,6 +16,13 @@ pub struct Texture {
     pub bind_group: wgpu::BindGroup,
 }
 
+/// `Textures` are only equal to themselves, their contents are never compared
+impl PartialEq for Texture {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self, other)
+    }
+}
+
 impl Texture {
     pub const DEPTH_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::Depth32Float; // 1.
     
@@ -75,28 +87,181 @@ impl Texture {
             ],
         };
 
+    /// Width and height in pixels
+    pub fn size(&self) -> (u32, u32) {
+        (self.texture.width(), self.texture.height())
+    }
+
+    /// Decodes an image in any format supported by the `image` crate
     pub fn from_bytes(
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         bytes: &[u8],
         label: &str,
-    ) -> Self {
-        let img = image::load_from_memory(bytes).unwrap();
+    ) -> Result<Self> {
+        let img = image::load_from_memory(bytes)?;
         Self::from_image(device, queue, &img, Some(label))
     }
 
+    /// Reads and decodes an image file, the path is used as label
+    pub fn from_path(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        path: impl AsRef<Path>,
+    ) -> Result<Self> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)?;
+        Self::from_bytes(device, queue, &bytes, &path.to_string_lossy())
+    }
+
     pub fn from_image(
         device: &wgpu::Device,
         queue: &wgpu::Queue,
         img: &image::DynamicImage,
         label: Option<&str>,
-    ) -> Self {
+    ) -> Result<Self> {
+        let (width, height) = img.dimensions();
+        Self::check_size(device, width, height)?;
         let rgba = img.to_rgba8();
-        let dimensions = img.dimensions();
+        let texture = Self::blank(device, width, height, label);
+        texture.write(queue, 0, 0, width, height, &rgba);
+        Ok(texture)
+    }
+
+    /// Creates a texture from RGBA pixels, 4 bytes per pixel, row by row
+    pub fn from_rgba(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        width: u32,
+        height: u32,
+        rgba: &[u8],
+        label: Option<&str>,
+    ) -> Result<Self> {
+        Self::check_size(device, width, height)?;
+        Self::check_length(width, height, rgba)?;
+        let texture = Self::blank(device, width, height, label);
+        texture.write(queue, 0, 0, width, height, rgba);
+        Ok(texture)
+    }
+
+    /// Wraps a texture owned by the app, for example one it renders a 3D scene into
+    ///
+    /// The view has to be sampleable as filterable float, like `Rgba8UnormSrgb`.
+    /// Keep drawing into `Texture::view` after passing it to an `Element`.
+    pub fn from_view(
+        device: &wgpu::Device,
+        texture: wgpu::Texture,
+        view: wgpu::TextureView,
+        label: Option<&str>,
+    ) -> Self {
+        let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
+            label,
+            address_mode_u: wgpu::AddressMode::ClampToEdge,
+            address_mode_v: wgpu::AddressMode::ClampToEdge,
+            address_mode_w: wgpu::AddressMode::ClampToEdge,
+            mag_filter: wgpu::FilterMode::Linear,
+            min_filter: wgpu::FilterMode::Linear,
+            mipmap_filter: wgpu::FilterMode::Nearest,
+            ..Default::default()
+        });
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            layout: &device.create_bind_group_layout(&Self::BIND_GROUP_LAYOUT),
+            entries: &[
+                wgpu::BindGroupEntry {
+                    binding: 0,
+                    resource: wgpu::BindingResource::TextureView(&view),
+                },
+                wgpu::BindGroupEntry {
+                    binding: 1,
+                    resource: wgpu::BindingResource::Sampler(&sampler),
+                },
+            ],
+            label,
+        });
+
+        Self {
+            texture,
+            view,
+            sampler,
+            bind_group,
+        }
+    }
+
+    /// Creates a texture that the app renders into and an `Element` shows
+    ///
+    /// Listen to `EventTypes::Resize` on the `Element` and create a new
+    /// target when its size changes.
+    pub fn render_target(
+        device: &wgpu::Device,
+        width: u32,
+        height: u32,
+        format: wgpu::TextureFormat,
+        label: Option<&str>,
+    ) -> Result<Self> {
+        Self::check_size(device, width, height)?;
+        let texture = device.create_texture(&wgpu::TextureDescriptor {
+            label,
+            size: wgpu::Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        });
+        let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+        Ok(Self::from_view(device, texture, view, label))
+    }
+
+    /// Magenta and black checkerboard, use in place of textures that failed to load
+    ///
+    /// ```ignore
+    /// let texture = Texture::from_path(device, queue, path)
+    ///     .unwrap_or_else(|_| Texture::placeholder(device, queue));
+    /// ```
+    pub fn placeholder(device: &wgpu::Device, queue: &wgpu::Queue) -> Self {
+        const SIZE: u32 = 8;
+        let rgba: Vec<u8> = (0..SIZE * SIZE)
+            .flat_map(|i| match (i % SIZE + i / SIZE) % 2 {
+                0 => [255, 0, 255, 255],
+                _ => [0, 0, 0, 255],
+            })
+            .collect();
+        let texture = Self::blank(device, SIZE, SIZE, Some("Placeholder"));
+        texture.write(queue, 0, 0, SIZE, SIZE, &rgba);
+        texture
+    }
+
+    /// Rejects sizes that the device can not create
+    fn check_size(device: &wgpu::Device, width: u32, height: u32) -> Result<()> {
+        let max = device.limits().max_texture_dimension_2d;
+        match width == 0 || height == 0 || width > max || height > max {
+            true => Err(Error::Size { width, height, max }),
+            false => Ok(()),
+        }
+    }
+
+    /// Rejects pixel data that does not match the size
+    fn check_length(width: u32, height: u32, rgba: &[u8]) -> Result<()> {
+        let expected = width as usize * height as usize * 4;
+        match rgba.len() == expected {
+            true => Ok(()),
+            false => Err(Error::DataLength {
+                expected,
+                found: rgba.len(),
+            }),
+        }
+    }
 
+    /// Creates a transparent texture, fill it using `Texture::write`
+    pub fn blank(device: &wgpu::Device, width: u32, height: u32, label: Option<&str>) -> Self {
         let size = wgpu::Extent3d {
-            width: dimensions.0,
-            height: dimensions.1,
+            width,
+            height,
             depth_or_array_layers: 1,
         };
         let texture = device.create_texture(&wgpu::TextureDescriptor {
@@ -110,22 +275,6 @@ impl Texture {
             view_formats: &[],
         });
 
-        queue.write_texture(
-            wgpu::ImageCopyTexture {
-                aspect: wgpu::TextureAspect::All,
-                texture: &texture,
-                mip_level: 0,
-                origin: wgpu::Origin3d::ZERO,
-            },
-            &rgba,
-            wgpu::ImageDataLayout {
-                offset: 0,
-                bytes_per_row: Some(4 * dimensions.0),
-                rows_per_image: Some(dimensions.1),
-            },
-            size,
-        );
-
         let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
         let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
             address_mode_u: wgpu::AddressMode::ClampToEdge,
@@ -159,4 +308,680 @@ impl Texture {
             bind_group,
         }
     }
+
+    /// Writes RGBA pixels into a rectangle of the texture
+    pub fn write(&self, queue: &wgpu::Queue, x: u32, y: u32, width: u32, height: u32, rgba: &[u8]) {
+        let size = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        queue.write_texture(
+            wgpu::ImageCopyTexture {
+                aspect: wgpu::TextureAspect::All,
+                texture: &self.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d { x, y, z: 0 },
+            },
+            rgba,
+            wgpu::ImageDataLayout {
+                offset: 0,
+                bytes_per_row: Some(4 * width),
+                rows_per_image: Some(height),
+            },
+            size,
+        );
+    }
+}
+
+/// Size of a border of a `NineSlice`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SliceInset {
+    /// Pixels of the `Texture`
+    Pixels(f32),
+    /// Fraction of the width or height of the `Texture`
+    Fraction(f32),
+}
+
+impl SliceInset {
+    fn pixels(&self, size: f32) -> f32 {
+        match self {
+            Self::Pixels(pixels) => *pixels,
+            Self::Fraction(fraction) => fraction * size,
+        }
+    }
+}
+
+/// Draws a `Texture` as a 3x3 grid so that its borders do not stretch
+///
+/// Corners keep their size, edges stretch along one axis and the center
+/// stretches along both. Borders shrink evenly when the `Element` is too small.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NineSlice {
+    pub left: SliceInset,
+    pub top: SliceInset,
+    pub right: SliceInset,
+    pub bottom: SliceInset,
+    /// Size of a texture pixel on screen
+    pub scale: f32,
+}
+
+impl NineSlice {
+    pub fn new(left: SliceInset, top: SliceInset, right: SliceInset, bottom: SliceInset) -> Self {
+        Self {
+            left,
+            top,
+            right,
+            bottom,
+            scale: 1.0,
+        }
+    }
+
+    /// Same inset on all sides
+    pub fn uniform(inset: SliceInset) -> Self {
+        Self::new(inset, inset, inset, inset)
+    }
+
+    /// Insets in pixels of the `Texture`
+    pub fn pixels(left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        Self::new(
+            SliceInset::Pixels(left),
+            SliceInset::Pixels(top),
+            SliceInset::Pixels(right),
+            SliceInset::Pixels(bottom),
+        )
+    }
+
+    /// Insets as fractions of the width and height of the `Texture`
+    pub fn fractions(left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        Self::new(
+            SliceInset::Fraction(left),
+            SliceInset::Fraction(top),
+            SliceInset::Fraction(right),
+            SliceInset::Fraction(bottom),
+        )
+    }
+
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Borders in texture coordinates and in pixels on screen
+    pub(crate) fn borders(&self, width: f32, height: f32) -> ([f32; 4], [f32; 4]) {
+        let pixels = [
+            self.left.pixels(width),
+            self.top.pixels(height),
+            self.right.pixels(width),
+            self.bottom.pixels(height),
+        ];
+        let size = [width, height, width, height];
+        let mut uv = [0.0; 4];
+        let mut px = [0.0; 4];
+        for i in 0..4 {
+            uv[i] = (pixels[i] / size[i].max(1.0)).clamp(0.0, 1.0);
+            px[i] = pixels[i].max(0.0) * self.scale;
+        }
+        (uv, px)
+    }
+}
+
+/// How a `Texture` is sized inside of its `Element`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Fit {
+    /// Stretches the texture over the whole `Element`
+    #[default]
+    Fill,
+    /// Largest size that keeps the ratio and fits inside of the `Element`
+    Contain,
+    /// Smallest size that keeps the ratio and covers the `Element`
+    Cover,
+    /// Keeps the size of the texture in pixels
+    None,
+    /// Same as `None` or `Contain`, whichever is smaller
+    ScaleDown,
+}
+
+impl Fit {
+    /// Size of an image of `natural` size inside of an `Element` of `size`
+    pub fn size(&self, natural: (f32, f32), size: (f32, f32)) -> (f32, f32) {
+        if natural.0 <= 0.0 || natural.1 <= 0.0 {
+            return size;
+        }
+        let contain = (size.0 / natural.0).min(size.1 / natural.1);
+        let scale = match self {
+            Self::Fill => return size,
+            Self::Contain => contain,
+            Self::Cover => (size.0 / natural.0).max(size.1 / natural.1),
+            Self::None => 1.0,
+            Self::ScaleDown => contain.min(1.0),
+        };
+        (natural.0 * scale, natural.1 * scale)
+    }
+}
+
+/// Where a `Texture` is placed when it does not fill its `Element`
+///
+/// 0.0 aligns to the left or top, 1.0 to the right or bottom
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ImageAlign {
+    pub x: f32,
+    pub y: f32,
+}
+
+impl ImageAlign {
+    pub const CENTER: Self = Self::new(0.5, 0.5);
+
+    pub const fn new(x: f32, y: f32) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Default for ImageAlign {
+    fn default() -> Self {
+        Self::CENTER
+    }
+}
+
+/// What is drawn outside of the fitted `Texture`
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Tiling {
+    /// Nothing
+    #[default]
+    None,
+    /// Copies of the texture
+    Repeat,
+    /// Copies of the texture, every other one flipped
+    Mirror,
+}
+
+/// Part of a `Texture` in texture coordinates, from 0.0 to 1.0
+///
+/// Used to draw a single sprite of a sprite sheet
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UvRect {
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl UvRect {
+    /// The whole texture
+    pub const FULL: Self = Self::new(0.0, 0.0, 1.0, 1.0);
+
+    pub const fn new(x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    /// Rectangle in pixels of a `Texture` of `size`
+    pub fn pixels(x: f32, y: f32, width: f32, height: f32, size: (u32, u32)) -> Self {
+        let (w, h) = (size.0.max(1) as f32, size.1.max(1) as f32);
+        Self::new(x / w, y / h, width / w, height / h)
+    }
+}
+
+impl Default for UvRect {
+    fn default() -> Self {
+        Self::FULL
+    }
+}
+
+/// Handle to a part of a `Texture`, see `Styles::set_region`
+///
+/// Returned by `TextureAtlas` and `SpriteSheet`
+#[derive(Debug, Clone)]
+pub struct AtlasRegion {
+    pub texture: Arc<Texture>,
+    pub uv: UvRect,
+    /// Width and height in pixels
+    pub size: (u32, u32),
+}
+
+impl AtlasRegion {
+    /// Region covering the whole `texture`
+    pub fn whole(texture: Arc<Texture>) -> Self {
+        let size = texture.size();
+        Self {
+            texture,
+            uv: UvRect::FULL,
+            size,
+        }
+    }
+
+    /// Part of this region, in pixels from its top left corner
+    pub fn sub(&self, x: u32, y: u32, width: u32, height: u32) -> Self {
+        let (w, h) = (self.size.0.max(1) as f32, self.size.1.max(1) as f32);
+        Self {
+            texture: self.texture.clone(),
+            uv: UvRect::new(
+                self.uv.x + x as f32 / w * self.uv.width,
+                self.uv.y + y as f32 / h * self.uv.height,
+                width as f32 / w * self.uv.width,
+                height as f32 / h * self.uv.height,
+            ),
+            size: (width, height),
+        }
+    }
+}
+
+/// Packs many images into a few shared textures
+///
+/// `Elements` showing regions of the same page share its texture memory and
+/// bind group instead of every icon owning its own texture.
+/// Pages are added when the images do not fit.
+///
+/// Draws are not batched: every `Element` is still drawn on its own and writes
+/// its own texture parameters, so an atlas saves memory and texture creation,
+/// not draw calls.
+pub struct TextureAtlas {
+    pages: Vec<AtlasPage>,
+    page_size: u32,
+    padding: u32,
+    regions: HashMap<String, AtlasRegion>,
+}
+
+struct AtlasPage {
+    texture: Arc<Texture>,
+    shelves: Vec<Shelf>,
+    /// Top of the space below the last shelf
+    bottom: u32,
+}
+
+/// Row of images, filled from the left
+struct Shelf {
+    y: u32,
+    height: u32,
+    x: u32,
+}
+
+impl AtlasPage {
+    /// Finds space for a rectangle on the shelf that wastes the least height
+    fn allocate(&mut self, width: u32, height: u32, size: u32) -> Option<(u32, u32)> {
+        let shelf = self
+            .shelves
+            .iter_mut()
+            .filter(|shelf| shelf.height >= height && size - shelf.x >= width)
+            .min_by_key(|shelf| shelf.height - height);
+        if let Some(shelf) = shelf {
+            let x = shelf.x;
+            shelf.x += width;
+            return Some((x, shelf.y));
+        }
+        if size - self.bottom < height {
+            return None;
+        }
+        let y = self.bottom;
+        self.shelves.push(Shelf { y, height, x: width });
+        self.bottom += height;
+        Some((0, y))
+    }
+}
+
+impl TextureAtlas {
+    /// Creates an empty atlas with square pages of `page_size` pixels
+    pub fn new(page_size: u32) -> Self {
+        Self {
+            pages: Vec::new(),
+            page_size,
+            padding: 1,
+            regions: HashMap::new(),
+        }
+    }
+
+    /// Transparent pixels around every image, keeps neighbours from bleeding in
+    ///
+    /// Defaults to 1
+    pub fn with_padding(mut self, padding: u32) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    /// Packs an image into the atlas
+    ///
+    /// Fails if the image is larger than a page.
+    /// Adding a name twice replaces the handle but does not free the old space.
+    pub fn add(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        name: impl Into<String>,
+        image: &image::DynamicImage,
+    ) -> Result<AtlasRegion> {
+        let rgba = image.to_rgba8();
+        self.add_rgba(device, queue, name, image.width(), image.height(), &rgba)
+    }
+
+    /// Packs raw RGBA pixels into the atlas, see `TextureAtlas::add`
+    pub fn add_rgba(
+        &mut self,
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        name: impl Into<String>,
+        width: u32,
+        height: u32,
+        rgba: &[u8],
+    ) -> Result<AtlasRegion> {
+        Texture::check_length(width, height, rgba)?;
+        let size = self.page_size;
+        let (w, h) = (width + self.padding * 2, height + self.padding * 2);
+        if width == 0 || height == 0 || w > size || h > size {
+            return Err(Error::Size {
+                width,
+                height,
+                max: size.saturating_sub(self.padding * 2),
+            });
+        }
+        let found = self
+            .pages
+            .iter_mut()
+            .enumerate()
+            .find_map(|(i, page)| page.allocate(w, h, size).map(|pos| (i, pos)));
+        let (page, (x, y)) = match found {
+            Some(found) => found,
+            None => {
+                let label = format!("Atlas Page {}", self.pages.len());
+                let mut page = AtlasPage {
+                    texture: Arc::new(Texture::blank(device, size, size, Some(&label))),
+                    shelves: Vec::new(),
+                    bottom: 0,
+                };
+                // an empty page always fits a rectangle smaller than the page
+                let pos = page.allocate(w, h, size).unwrap_or_default();
+                self.pages.push(page);
+                (self.pages.len() - 1, pos)
+            }
+        };
+        let (x, y) = (x + self.padding, y + self.padding);
+        let texture = self.pages[page].texture.clone();
+        texture.write(queue, x, y, width, height, rgba);
+        let region = AtlasRegion {
+            texture,
+            uv: UvRect::pixels(x as f32, y as f32, width as f32, height as f32, (size, size)),
+            size: (width, height),
+        };
+        self.regions.insert(name.into(), region.clone());
+        Ok(region)
+    }
+
+    /// Handle of an image added under `name`
+    pub fn get(&self, name: &str) -> Option<&AtlasRegion> {
+        self.regions.get(name)
+    }
+
+    /// Textures of the pages in the order they were created
+    pub fn pages(&self) -> impl Iterator<Item = &Arc<Texture>> {
+        self.pages.iter().map(|page| &page.texture)
+    }
+}
+
+/// Frames cut out of a single image, addressed by index or name
+#[derive(Debug, Clone, Default)]
+pub struct SpriteSheet {
+    pub frames: Vec<AtlasRegion>,
+    names: HashMap<String, usize>,
+}
+
+impl SpriteSheet {
+    /// Splits `region` into `columns` by `rows` equal cells, row by row
+    ///
+    /// Use `AtlasRegion::whole` for sheets that are not part of an atlas
+    pub fn grid(region: &AtlasRegion, columns: u32, rows: u32) -> Self {
+        let (width, height) = (region.size.0 / columns.max(1), region.size.1 / rows.max(1));
+        let frames = (0..rows)
+            .flat_map(|row| (0..columns).map(move |column| (column, row)))
+            .map(|(column, row)| region.sub(column * width, row * height, width, height))
+            .collect();
+        Self {
+            frames,
+            names: HashMap::new(),
+        }
+    }
+
+    /// Reads frames of `region` from a JSON description
+    ///
+    /// Supports the hash and array formats exported by TexturePacker and Aseprite,
+    /// where every frame has a `frame` object with `x`, `y`, `w` and `h` in pixels.
+    /// Frames of the hash format are sorted by name.
+    #[cfg(feature = "json")]
+    pub fn from_json(region: &AtlasRegion, json: &str) -> Result<Self> {
+        let value: serde_json::Value = serde_json::from_str(json)?;
+        let missing = |field: &str| Error::SpriteSheet(format!("missing `{}`", field));
+        let frame = |value: &serde_json::Value| {
+            let frame = value.get("frame").ok_or_else(|| missing("frame"))?;
+            let number = |key: &str| {
+                let number = frame.get(key).and_then(|n| n.as_u64());
+                number.map(|n| n as u32).ok_or_else(|| missing(key))
+            };
+            Ok::<_, Error>(region.sub(number("x")?, number("y")?, number("w")?, number("h")?))
+        };
+        let mut sheet = Self::default();
+        match value.get("frames") {
+            Some(serde_json::Value::Object(frames)) => {
+                for (name, value) in frames {
+                    sheet.push(name, frame(value)?);
+                }
+            }
+            Some(serde_json::Value::Array(frames)) => {
+                for value in frames {
+                    let name = value.get("filename").and_then(|name| name.as_str());
+                    sheet.push(name.unwrap_or_default(), frame(value)?);
+                }
+            }
+            _ => return Err(missing("frames")),
+        }
+        Ok(sheet)
+    }
+
+    #[cfg(feature = "json")]
+    fn push(&mut self, name: &str, frame: AtlasRegion) {
+        if !name.is_empty() {
+            self.names.insert(name.to_string(), self.frames.len());
+        }
+        self.frames.push(frame);
+    }
+
+    pub fn frame(&self, index: usize) -> Option<&AtlasRegion> {
+        self.frames.get(index)
+    }
+
+    /// Frame with a name from a JSON description
+    pub fn get(&self, name: &str) -> Option<&AtlasRegion> {
+        self.frame(*self.names.get(name)?)
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+/// Frames of an animated image with their delays
+///
+/// Frames are packed into a `TextureAtlas`. Show it on an `Element` using
+/// `Element::set_animated_texture`, frames advance with `Gui::advance`.
+#[derive(Debug, Clone)]
+pub struct AnimatedTexture {
+    pub frames: Vec<AtlasRegion>,
+    /// Time each frame is shown, in seconds
+    pub delays: Vec<f32>,
+}
+
+impl AnimatedTexture {
+    /// Frames without a delay are shown for this long, like in browsers
+    const DEFAULT_DELAY: f32 = 0.1;
+
+    /// Decodes an animated GIF, APNG or WebP
+    ///
+    /// Other images, or still images in these formats, have a single frame
+    pub fn from_bytes(device: &wgpu::Device, queue: &wgpu::Queue, bytes: &[u8]) -> Result<Self> {
+        use image::{codecs, AnimationDecoder, ImageFormat};
+        let reader = std::io::Cursor::new(bytes);
+        let frames = match image::guess_format(bytes)? {
+            ImageFormat::Gif => codecs::gif::GifDecoder::new(reader)?.into_frames(),
+            ImageFormat::Png => {
+                let decoder = codecs::png::PngDecoder::new(reader)?;
+                match decoder.is_apng()? {
+                    true => decoder.apng()?.into_frames(),
+                    false => return Self::still(device, queue, bytes),
+                }
+            }
+            ImageFormat::WebP => {
+                let decoder = codecs::webp::WebPDecoder::new(reader)?;
+                match decoder.has_animation() {
+                    true => decoder.into_frames(),
+                    false => return Self::still(device, queue, bytes),
+                }
+            }
+            _ => return Self::still(device, queue, bytes),
+        };
+        Self::from_frames(device, queue, frames.collect_frames()?)
+    }
+
+    /// Reads and decodes an animated image file, see `AnimatedTexture::from_bytes`
+    pub fn from_path(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        path: impl AsRef<Path>,
+    ) -> Result<Self> {
+        Self::from_bytes(device, queue, &std::fs::read(path)?)
+    }
+
+    /// Uploads decoded frames
+    pub fn from_frames(
+        device: &wgpu::Device,
+        queue: &wgpu::Queue,
+        frames: Vec<image::Frame>,
+    ) -> Result<Self> {
+        let largest = frames.iter().fold(1, |largest, frame| {
+            let buffer = frame.buffer();
+            largest.max(buffer.width()).max(buffer.height())
+        });
+        let page_size = device
+            .limits()
+            .max_texture_dimension_2d
+            .min((largest + 2).next_power_of_two().max(1024));
+        let mut atlas = TextureAtlas::new(page_size);
+        let mut animated = Self {
+            frames: Vec::with_capacity(frames.len()),
+            delays: Vec::with_capacity(frames.len()),
+        };
+        for (i, frame) in frames.into_iter().enumerate() {
+            let (numer, denom) = frame.delay().numer_denom_ms();
+            let delay = numer as f32 / denom.max(1) as f32 / 1000.0;
+            let buffer = frame.into_buffer();
+            let (width, height) = buffer.dimensions();
+            let region = atlas.add_rgba(device, queue, i.to_string(), width, height, &buffer)?;
+            animated.frames.push(region);
+            animated
+                .delays
+                .push(if delay > 0.0 { delay } else { Self::DEFAULT_DELAY });
+        }
+        Ok(animated)
+    }
+
+    fn still(device: &wgpu::Device, queue: &wgpu::Queue, bytes: &[u8]) -> Result<Self> {
+        let texture = Texture::from_bytes(device, queue, bytes, "Animated Texture")?;
+        Ok(Self {
+            frames: vec![AtlasRegion::whole(Arc::new(texture))],
+            delays: vec![Self::DEFAULT_DELAY],
+        })
+    }
+
+    /// Length of one loop in seconds
+    pub fn duration(&self) -> f32 {
+        self.delays.iter().sum()
+    }
+
+    /// Index of the frame shown `time` seconds after the start
+    pub fn frame_at(&self, time: f32, looping: bool) -> usize {
+        let duration = self.duration();
+        let mut time = match looping && duration > 0.0 {
+            true => time.rem_euclid(duration),
+            false => time,
+        };
+        for (i, delay) in self.delays.iter().enumerate() {
+            if time < *delay {
+                return i;
+            }
+            time -= delay;
+        }
+        self.frames.len().saturating_sub(1)
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+/// Playback of an `AnimatedTexture` on an `Element`
+#[derive(Debug, Clone)]
+pub struct Playback {
+    pub texture: Arc<AnimatedTexture>,
+    /// Seconds since the first frame
+    pub time: f32,
+    pub playing: bool,
+    /// Starts over after the last frame, otherwise stops on it
+    pub looping: bool,
+    /// Frame that is currently shown
+    frame: Option<usize>,
+}
+
+impl Playback {
+    /// Plays `texture` in a loop from the first frame
+    pub fn new(texture: Arc<AnimatedTexture>) -> Self {
+        Self {
+            texture,
+            time: 0.0,
+            playing: true,
+            looping: true,
+            frame: None,
+        }
+    }
+
+    pub fn play(&mut self) {
+        self.playing = true;
+    }
+
+    pub fn pause(&mut self) {
+        self.playing = false;
+    }
+
+    /// Jumps back to the first frame
+    pub fn restart(&mut self) {
+        self.time = 0.0;
+    }
+
+    /// Index of the frame that is currently shown
+    pub fn frame(&self) -> usize {
+        self.frame.unwrap_or_default()
+    }
+
+    /// Moves the clock by `delta` seconds, returns the frame to show if it changed
+    pub(crate) fn advance(&mut self, delta: f32) -> Option<AtlasRegion> {
+        if self.playing {
+            self.time += delta;
+        }
+        if !self.looping && self.time >= self.texture.duration() {
+            self.playing = false;
+        }
+        let frame = self.texture.frame_at(self.time, self.looping);
+        if self.frame == Some(frame) {
+            return None;
+        }
+        self.frame = Some(frame);
+        self.texture.frames.get(frame).cloned()
+    }
 }
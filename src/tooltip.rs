@@ -0,0 +1,152 @@
+//! Tooltips shown after hovering an `Element`, see `Element::with_tooltip`
+
+use crate::{
+    styles::styles_proposition::{Colors, Styles, Values},
+    ElementKey, ElementTransform, Gui, Point,
+};
+
+/// What is shown inside of the tooltip
+#[derive(Debug, Clone)]
+pub enum TooltipContent {
+    /// Text inside of an `Element` created by the `Gui`
+    ///
+    /// The `Element` uses the style class `"tooltip"`,
+    /// the `Gui` provides a default look, see `Gui::set_default_class`
+    Text(String),
+    /// Existing `Element` with its children
+    Element(ElementKey),
+}
+
+/// Preferred side of the tooltip
+///
+/// The tooltip flips to the opposite side if it does not fit into the view port
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Placement {
+    #[default]
+    Below,
+    Above,
+    Left,
+    Right,
+    /// Next to the cursor at the time the tooltip is shown
+    Cursor,
+}
+
+/// Tooltip attached to an `Element`
+#[derive(Debug, Clone)]
+pub struct Tooltip {
+    pub content: TooltipContent,
+    /// Seconds of hovering before the tooltip is shown, measured by `Gui::advance`
+    pub delay: f32,
+    pub placement: Placement,
+}
+
+impl Tooltip {
+    pub fn text(text: &str) -> Self {
+        Self::new(TooltipContent::Text(text.to_string()))
+    }
+
+    pub fn element(key: ElementKey) -> Self {
+        Self::new(TooltipContent::Element(key))
+    }
+
+    pub fn new(content: TooltipContent) -> Self {
+        Self {
+            content,
+            delay: 0.5,
+            placement: Placement::default(),
+        }
+    }
+
+    pub fn with_delay(mut self, delay: f32) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn with_placement(mut self, placement: Placement) -> Self {
+        self.placement = placement;
+        self
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct TooltipState {
+    /// Hovered `Element` with a tooltip
+    pub target: Option<ElementKey>,
+    /// Time of entering the target
+    pub since: f32,
+    /// Root of the shown tooltip
+    pub shown: Option<ElementKey>,
+    /// The root was created by the `Gui` and is removed with the tooltip
+    pub owned: bool,
+    /// Cursor position when the tooltip was shown
+    pub cursor: Point,
+    /// Tooltip was dismissed and is not shown until the target is entered again
+    pub dismissed: bool,
+}
+
+impl Placement {
+    /// Returns the center of a tooltip of `size` placed next to `target`
+    pub(crate) fn place(
+        &self,
+        target: &ElementTransform,
+        size: Point,
+        cursor: Point,
+        view_port: Point,
+        gap: f32,
+    ) -> Point {
+        let (half_w, half_h) = (size.x / 2.0, size.y / 2.0);
+        let left = target.position.x - target.scale.x / 2.0;
+        let right = target.position.x + target.scale.x / 2.0;
+        let top = target.position.y - target.scale.y / 2.0;
+        let bottom = target.position.y + target.scale.y / 2.0;
+        let below = bottom + gap + half_h;
+        let above = top - gap - half_h;
+        let after = right + gap + half_w;
+        let before = left - gap - half_w;
+        let (x, y) = match self {
+            Placement::Below if below + half_h > view_port.y => (target.position.x, above),
+            Placement::Below => (target.position.x, below),
+            Placement::Above if above - half_h < 0.0 => (target.position.x, below),
+            Placement::Above => (target.position.x, above),
+            Placement::Right if after + half_w > view_port.x => (before, target.position.y),
+            Placement::Right => (after, target.position.y),
+            Placement::Left if before - half_w < 0.0 => (after, target.position.y),
+            Placement::Left => (before, target.position.y),
+            Placement::Cursor => {
+                let x = match cursor.x + gap + size.x > view_port.x {
+                    true => cursor.x - gap - half_w,
+                    false => cursor.x + gap + half_w,
+                };
+                let y = match cursor.y + gap + size.y > view_port.y {
+                    true => cursor.y - gap - half_h,
+                    false => cursor.y + gap + half_h,
+                };
+                (x, y)
+            }
+        };
+        Point::new(clamp(x, half_w, view_port.x), clamp(y, half_h, view_port.y))
+    }
+}
+
+/// Keeps the tooltip inside of the view port, centers it if it is too large
+fn clamp(center: f32, half: f32, max: f32) -> f32 {
+    if half * 2.0 >= max {
+        return max / 2.0;
+    }
+    center.max(half).min(max - half)
+}
+
+impl<Msg: Clone> Gui<Msg> {
+    /// Adds the default styles of text tooltips, see `Gui::set_default_class`
+    pub(crate) fn default_tooltip_class(&mut self) {
+        let theme = |name: &str| Colors::Theme(name.to_string(), None);
+        let mut tooltip = Styles::default();
+        tooltip.width.set(Values::logical(240.0));
+        tooltip.height.set(Values::logical(32.0));
+        tooltip.bg_color.set(theme("surface"));
+        tooltip.text_color.set(theme("text"));
+        tooltip.text_size.set(Values::theme("font_size_small"));
+        tooltip.edges_radius.set(Values::theme("radius"));
+        self.set_default_class("tooltip", tooltip);
+    }
+}
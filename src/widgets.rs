@@ -0,0 +1,2480 @@
+//! Reusable widgets composed from `Elements`
+//!
+//! Widgets are built into a `Gui` and return the key of their root `Element`.
+//! Value changes are reported as events with `EventTypes::Change` and the `Msg`
+//! returned by the `on_change` function of the widget.
+//!
+//! ```ignore
+//! enum Msg {
+//!     Volume(f32),
+//!     Fullscreen(bool),
+//!     Apply,
+//! }
+//!
+//! let volume = Slider::new(0.0, 1.0, 0.5).on_change(Msg::Volume).build(&mut gui);
+//! let fullscreen = Toggle::new(false).with_label("Fullscreen").on_change(Msg::Fullscreen).build(&mut gui);
+//! let apply = Button::new("Apply").on_click(Msg::Apply).build(&mut gui);
+//! ```
+//!
+//! Widgets are styled with classes. Defaults of these classes are kept below the
+//! `StyleSheet` of the `Gui`, classes of the sheet override them property by property:
+//! - `button`
+//! - `checkbox`, `checkbox-box`, `checkbox-mark`, `checkbox-label`
+//! - `toggle`, `toggle-track`, `toggle-knob`, `toggle-label`
+//! - `radio-group`, `radio-option`, `radio`, `radio-dot`, `radio-option-label`
+//! - `slider`, `slider-track`, `slider-fill`, `slider-knob`
+//! - `progress`, `progress-fill`
+//! - `dropdown`, `dropdown-list`, `dropdown-item`
+//! - `list-box`, `list-item`
+//! - `virtual-list`, `virtual-list-row`, `virtual-list-thumb`
+//! - `tab-view`, `tab-strip`, `tab`, `tab-body`
+//! - `split-pane`, `split-divider`
+//! - `collapsible`, `collapsible-header`, `collapsible-body`
+//! - `desktop`, `dock-preview`, `window`, `window-title`, `window-title-text`,
+//!   `window-button`, `window-body`, `window-grip`
+//!
+//! The box of a checked checkbox, the track of a toggle that is on, the selected
+//! radio button, selected items of dropdowns and list boxes, the selected tab and
+//! the header of an expanded collapsible and the minimize button of a minimized
+//! window get the class `checked`.
+//!
+//! Dropdowns and list boxes are selectable, pressing enter on a selected dropdown
+//! opens its list and pressing enter or space on a selected item picks it.
+
+use crate::{
+    animation::{Easing, Transition, Transitions},
+    events::{ElementEvent, Event, EventTypes, WindowEvent},
+    styles::styles_proposition::{Colors, Position, PositionValues, Side, Styles, Values},
+    Children, Element, ElementKey, ElementTransform, Gui, Layer, Point, Section,
+};
+
+/// Value of a widget
+#[derive(Debug, Clone, PartialEq)]
+pub enum WidgetValue {
+    /// Checkbox and toggle
+    Bool(bool),
+    /// Slider and progress bar
+    Number(f32),
+    /// Selected option of a radio group or a dropdown
+    Index(usize),
+    /// Selected options of a list box in ascending order
+    Indices(Vec<usize>),
+}
+
+/// Behaviour and state of a widget, stored on its root `Element`
+pub(crate) enum Widget<Msg: Clone> {
+    Button {
+        on_click: Option<Msg>,
+    },
+    Checkbox {
+        checked: bool,
+        control: ElementKey,
+        mark: ElementKey,
+        on_change: Option<fn(bool) -> Msg>,
+    },
+    Toggle {
+        on: bool,
+        control: ElementKey,
+        knob: ElementKey,
+        on_change: Option<fn(bool) -> Msg>,
+    },
+    Radio {
+        selected: usize,
+        options: Vec<RadioOption>,
+        on_change: Option<fn(usize) -> Msg>,
+    },
+    Slider {
+        min: f32,
+        max: f32,
+        step: Option<f32>,
+        value: f32,
+        fill: ElementKey,
+        knob: ElementKey,
+        on_change: Option<fn(f32) -> Msg>,
+    },
+    Progress {
+        value: f32,
+        fill: ElementKey,
+    },
+    Dropdown {
+        selected: usize,
+        list: ElementKey,
+        rows: Rows,
+        on_change: Option<fn(usize) -> Msg>,
+    },
+    /// List of a dropdown, forwards input to the dropdown
+    DropdownList {
+        dropdown: ElementKey,
+    },
+    ListBox {
+        selected: Vec<usize>,
+        multiple: bool,
+        rows: Rows,
+        on_change: Option<fn(Vec<usize>) -> Msg>,
+    },
+    VirtualList {
+        source: Box<dyn ListSource<Msg>>,
+        /// Index of the item shown in the first row
+        scroll: usize,
+        rows: Vec<ElementKey>,
+        thumb: ElementKey,
+        selected: Option<usize>,
+        on_select: Option<fn(usize) -> Msg>,
+    },
+    Tabs {
+        selected: usize,
+        tabs: Vec<ElementKey>,
+        pages: Vec<ElementKey>,
+        on_change: Option<fn(usize) -> Msg>,
+    },
+    Split {
+        /// Panes are placed in rows instead of columns
+        rows: bool,
+        /// Part of the pane taken by the first section
+        ratio: f32,
+        divider: ElementKey,
+        /// The divider was pressed
+        dragging: bool,
+        on_change: Option<fn(f32) -> Msg>,
+    },
+    Collapsible {
+        expanded: bool,
+        header: ElementKey,
+        body: ElementKey,
+        on_change: Option<fn(bool) -> Msg>,
+    },
+    Desktop {
+        preview: ElementKey,
+        /// Highest `z_index` given to a window
+        top: i32,
+    },
+    Window(Box<WindowState<Msg>>),
+}
+
+/// State of a `FloatingWindow`
+pub(crate) struct WindowState<Msg> {
+    desktop: ElementKey,
+    title_bar: ElementKey,
+    body: ElementKey,
+    minimize: Option<ElementKey>,
+    close: Option<ElementKey>,
+    grip: Option<ElementKey>,
+    /// Position relative to the desktop and size of the floating window in pixels
+    rect: [f32; 4],
+    open: bool,
+    minimized: bool,
+    dock: Option<Dock>,
+    drag: WindowDrag,
+    on_close: Option<Msg>,
+}
+
+#[derive(Clone, Copy)]
+enum WindowDrag {
+    None,
+    /// Cursor position relative to the top left corner of the window
+    Move(Point),
+    /// Cursor position relative to the size of the window
+    Resize(Point),
+}
+
+/// Height of the title bar of windows in logical pixels
+const TITLE_HEIGHT: f32 = 28.0;
+
+/// Region of a `Desktop` that a window is docked into
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Dock {
+    Left,
+    Right,
+    Top,
+    Bottom,
+    /// Whole desktop
+    Fill,
+}
+
+impl Dock {
+    /// Position, width and height of the region in the desktop
+    fn place(&self) -> (Position, Values, Values) {
+        let (anchor, width, height) = match self {
+            Dock::Left => (PositionValues::TopLeft, 50.0, 100.0),
+            Dock::Right => (PositionValues::TopRight, 50.0, 100.0),
+            Dock::Top => (PositionValues::TopLeft, 100.0, 50.0),
+            Dock::Bottom => (PositionValues::BottomLeft, 100.0, 50.0),
+            Dock::Fill => (PositionValues::TopLeft, 100.0, 100.0),
+        };
+        let width = Values::container(width, Side::Width);
+        let height = Values::container(height, Side::Height);
+        (Position::new(anchor).with_pivot(anchor), width, height)
+    }
+}
+
+
+impl<Msg: Clone> Widget<Msg> {
+    fn value(&self) -> Option<WidgetValue> {
+        match self {
+            Widget::Button { .. } => None,
+            Widget::Checkbox { checked, .. } => Some(WidgetValue::Bool(*checked)),
+            Widget::Toggle { on, .. } => Some(WidgetValue::Bool(*on)),
+            Widget::Radio { selected, .. } => Some(WidgetValue::Index(*selected)),
+            Widget::Slider { value, .. } => Some(WidgetValue::Number(*value)),
+            Widget::Progress { value, .. } => Some(WidgetValue::Number(*value)),
+            Widget::Dropdown { selected, .. } => Some(WidgetValue::Index(*selected)),
+            Widget::DropdownList { .. } => None,
+            Widget::ListBox { selected, .. } => Some(WidgetValue::Indices(selected.clone())),
+            Widget::VirtualList { selected, .. } => selected.map(WidgetValue::Index),
+            Widget::Tabs { selected, .. } => Some(WidgetValue::Index(*selected)),
+            Widget::Split { ratio, .. } => Some(WidgetValue::Number(*ratio)),
+            Widget::Collapsible { expanded, .. } => Some(WidgetValue::Bool(*expanded)),
+            Widget::Desktop { .. } => None,
+            Widget::Window(window) => Some(WidgetValue::Bool(window.open)),
+        }
+    }
+
+    fn rows(&self) -> Option<&Rows> {
+        match self {
+            Widget::Dropdown { rows, .. } | Widget::ListBox { rows, .. } => Some(rows),
+            _ => None,
+        }
+    }
+
+    /// Stores the value, returns false if the widget does not take such values
+    fn set_value(&mut self, new: WidgetValue) -> bool {
+        match (self, new) {
+            (Widget::Checkbox { checked, .. }, WidgetValue::Bool(new)) => *checked = new,
+            (Widget::Toggle { on, .. }, WidgetValue::Bool(new)) => *on = new,
+            (Widget::Radio { selected, options, .. }, WidgetValue::Index(new)) => {
+                *selected = new.min(options.len().saturating_sub(1))
+            }
+            (
+                Widget::Slider {
+                    min,
+                    max,
+                    step,
+                    value,
+                    ..
+                },
+                WidgetValue::Number(new),
+            ) => *value = snap(new, *min, *max, *step),
+            (Widget::Progress { value, .. }, WidgetValue::Number(new)) => {
+                *value = new.clamp(0.0, 1.0)
+            }
+            (Widget::Dropdown { selected, rows, .. }, WidgetValue::Index(new)) => {
+                *selected = new.min(rows.options.len().saturating_sub(1))
+            }
+            (
+                Widget::ListBox {
+                    selected,
+                    multiple,
+                    rows,
+                    ..
+                },
+                WidgetValue::Indices(mut new),
+            ) => {
+                new.retain(|i| *i < rows.options.len());
+                new.sort_unstable();
+                new.dedup();
+                if !*multiple {
+                    new.truncate(1);
+                }
+                *selected = new;
+            }
+            (Widget::VirtualList { source, selected, .. }, WidgetValue::Index(new)) => {
+                *selected = Some(new).filter(|new| *new < source.len())
+            }
+            (Widget::Tabs { selected, pages, .. }, WidgetValue::Index(new)) => {
+                *selected = new.min(pages.len().saturating_sub(1))
+            }
+            (Widget::Split { ratio, .. }, WidgetValue::Number(new)) => *ratio = new.clamp(0.0, 1.0),
+            (Widget::Collapsible { expanded, .. }, WidgetValue::Bool(new)) => *expanded = new,
+            (Widget::Window(window), WidgetValue::Bool(new)) => window.open = new,
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// `Elements` of an option of a radio group
+pub(crate) struct RadioOption {
+    option: ElementKey,
+    control: ElementKey,
+    dot: ElementKey,
+}
+
+/// Options shown in a fixed number of recycled `Elements`
+pub(crate) struct Rows {
+    options: Vec<String>,
+    /// Index of the option shown in the first row
+    scroll: usize,
+    elements: Vec<ElementKey>,
+}
+
+impl Rows {
+    /// Creates at most `max_rows` selectable rows with `class`
+    fn new<Msg: Clone>(
+        gui: &mut Gui<Msg>,
+        class: &str,
+        options: Vec<String>,
+        max_rows: usize,
+    ) -> Self {
+        let elements = (0..options.len().min(max_rows.max(1)))
+            .map(|_| {
+                let mut element = Element::new().with_class(class);
+                element.styles.selectable = true;
+                gui.add_element(element)
+            })
+            .collect();
+        Self {
+            options,
+            scroll: 0,
+            elements,
+        }
+    }
+
+    fn sections(&self) -> Children {
+        Children::Rows {
+            children: self
+                .elements
+                .iter()
+                .map(|element| Section {
+                    element: *element,
+                    size: None,
+                })
+                .collect(),
+            spacing: None,
+        }
+    }
+
+    /// Index of the option shown in `key`
+    fn index_of(&self, key: ElementKey) -> Option<usize> {
+        let row = self.elements.iter().position(|e| *e == key)?;
+        Some(self.scroll + row).filter(|i| *i < self.options.len())
+    }
+
+    /// Scrolls by one row against the direction of `delta`, returns false if nothing moved
+    fn scroll(&mut self, delta: f32) -> bool {
+        let max = self.options.len() - self.elements.len();
+        let scroll = match delta {
+            delta if delta > 0.0 => self.scroll.saturating_sub(1),
+            delta if delta < 0.0 => (self.scroll + 1).min(max),
+            _ => self.scroll,
+        };
+        let moved = scroll != self.scroll;
+        self.scroll = scroll;
+        moved
+    }
+
+    /// Scrolls so that the option at `index` is shown
+    fn reveal(&mut self, index: usize) {
+        if index < self.scroll {
+            self.scroll = index;
+        } else if index >= self.scroll + self.elements.len() {
+            self.scroll = index + 1 - self.elements.len();
+        }
+    }
+
+    fn show<Msg: Clone>(&self, gui: &mut Gui<Msg>, selected: impl Fn(usize) -> bool) {
+        for (row, key) in self.elements.iter().enumerate() {
+            let index = self.scroll + row;
+            if let Some(element) = gui.get_element_mut(*key) {
+                element.set_text(self.options.get(index).cloned());
+                match selected(index) {
+                    true => element.add_class("checked"),
+                    false => element.remove_class("checked"),
+                }
+            }
+        }
+    }
+}
+
+/// Clamps `value` between `min` and `max` and rounds it to `step`
+fn snap(value: f32, min: f32, max: f32, step: Option<f32>) -> f32 {
+    let value = match step {
+        Some(step) if step > 0.0 => min + ((value - min) / step).round() * step,
+        _ => value,
+    };
+    value.clamp(min.min(max), max.max(min))
+}
+
+/// Fraction of the range that `value` takes
+fn fraction(value: f32, min: f32, max: f32) -> f32 {
+    if max == min {
+        return 0.0;
+    }
+    ((value - min) / (max - min)).clamp(0.0, 1.0)
+}
+
+/// Clickable button with text
+pub struct Button<Msg> {
+    text: String,
+    on_click: Option<Msg>,
+}
+
+impl<Msg: Clone> Button<Msg> {
+    pub fn new(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            on_click: None,
+        }
+    }
+
+    /// Message emitted with `EventTypes::Click`
+    pub fn on_click(mut self, msg: Msg) -> Self {
+        self.on_click = Some(msg);
+        self
+    }
+
+    pub fn build(self, gui: &mut Gui<Msg>) -> ElementKey {
+        gui.default_widget_classes();
+        let mut element = Element::new().with_class("button");
+        element.set_text(Some(self.text));
+        element.widget = Some(Widget::Button {
+            on_click: self.on_click,
+        });
+        gui.add_element(element)
+    }
+}
+
+/// Box that can be checked, with an optional label
+pub struct Checkbox<Msg> {
+    checked: bool,
+    label: Option<String>,
+    on_change: Option<fn(bool) -> Msg>,
+}
+
+impl<Msg: Clone> Checkbox<Msg> {
+    pub fn new(checked: bool) -> Self {
+        Self {
+            checked,
+            label: None,
+            on_change: None,
+        }
+    }
+
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    pub fn on_change(mut self, on_change: fn(bool) -> Msg) -> Self {
+        self.on_change = Some(on_change);
+        self
+    }
+
+    pub fn build(self, gui: &mut Gui<Msg>) -> ElementKey {
+        gui.default_widget_classes();
+        let mark = gui.add_element(Element::new().with_class("checkbox-mark"));
+        let check = gui.add_element(
+            Element::new()
+                .with_class("checkbox-box")
+                .with_children(Children::Element(mark)),
+        );
+        let root = labeled(gui, "checkbox", check, self.label);
+        gui.attach_widget(
+            root,
+            Widget::Checkbox {
+                checked: self.checked,
+                control: check,
+                mark,
+                on_change: self.on_change,
+            },
+        );
+        root
+    }
+}
+
+/// Switch between on and off, with an optional label
+pub struct Toggle<Msg> {
+    on: bool,
+    label: Option<String>,
+    on_change: Option<fn(bool) -> Msg>,
+}
+
+impl<Msg: Clone> Toggle<Msg> {
+    pub fn new(on: bool) -> Self {
+        Self {
+            on,
+            label: None,
+            on_change: None,
+        }
+    }
+
+    pub fn with_label(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    pub fn on_change(mut self, on_change: fn(bool) -> Msg) -> Self {
+        self.on_change = Some(on_change);
+        self
+    }
+
+    pub fn build(self, gui: &mut Gui<Msg>) -> ElementKey {
+        gui.default_widget_classes();
+        let knob = gui.add_element(Element::new().with_class("toggle-knob"));
+        let track = gui.add_element(
+            Element::new()
+                .with_class("toggle-track")
+                .with_children(Children::Element(knob)),
+        );
+        let root = labeled(gui, "toggle", track, self.label);
+        gui.attach_widget(
+            root,
+            Widget::Toggle {
+                on: self.on,
+                control: track,
+                knob,
+                on_change: self.on_change,
+            },
+        );
+        root
+    }
+}
+
+/// List of options of which one is selected
+pub struct RadioGroup<Msg> {
+    options: Vec<String>,
+    selected: usize,
+    on_change: Option<fn(usize) -> Msg>,
+}
+
+impl<Msg: Clone> RadioGroup<Msg> {
+    pub fn new(options: &[&str], selected: usize) -> Self {
+        Self {
+            options: options.iter().map(|o| o.to_string()).collect(),
+            selected,
+            on_change: None,
+        }
+    }
+
+    /// Called with the index of the selected option
+    pub fn on_change(mut self, on_change: fn(usize) -> Msg) -> Self {
+        self.on_change = Some(on_change);
+        self
+    }
+
+    pub fn build(self, gui: &mut Gui<Msg>) -> ElementKey {
+        gui.default_widget_classes();
+        let mut options = Vec::new();
+        let mut sections = Vec::new();
+        for option in self.options {
+            let dot = gui.add_element(Element::new().with_class("radio-dot"));
+            let radio = gui.add_element(
+                Element::new()
+                    .with_class("radio")
+                    .with_children(Children::Element(dot)),
+            );
+            let item = labeled(gui, "radio-option", radio, Some(option));
+            options.push(RadioOption {
+                option: item,
+                control: radio,
+                dot,
+            });
+            sections.push(Section {
+                element: item,
+                size: None,
+            });
+        }
+        let root = gui.add_element(
+            Element::new()
+                .with_class("radio-group")
+                .with_children(Children::Rows {
+                    children: sections,
+                    spacing: None,
+                }),
+        );
+        gui.attach_widget(
+            root,
+            Widget::Radio {
+                selected: self.selected,
+                options,
+                on_change: self.on_change,
+            },
+        );
+        root
+    }
+}
+
+/// Value in a range picked by dragging
+pub struct Slider<Msg> {
+    min: f32,
+    max: f32,
+    value: f32,
+    step: Option<f32>,
+    on_change: Option<fn(f32) -> Msg>,
+}
+
+impl<Msg: Clone> Slider<Msg> {
+    pub fn new(min: f32, max: f32, value: f32) -> Self {
+        Self {
+            min,
+            max,
+            value,
+            step: None,
+            on_change: None,
+        }
+    }
+
+    /// Rounds the value to multiples of `step` from `min`
+    pub fn with_step(mut self, step: f32) -> Self {
+        self.step = Some(step);
+        self
+    }
+
+    pub fn on_change(mut self, on_change: fn(f32) -> Msg) -> Self {
+        self.on_change = Some(on_change);
+        self
+    }
+
+    pub fn build(self, gui: &mut Gui<Msg>) -> ElementKey {
+        gui.default_widget_classes();
+        let fill = gui.add_element(Element::new().with_class("slider-fill"));
+        let track = gui.add_element(
+            Element::new()
+                .with_class("slider-track")
+                .with_children(Children::Element(fill)),
+        );
+        let knob = gui.add_element(Element::new().with_class("slider-knob"));
+        let root = gui.add_element(
+            Element::new()
+                .with_class("slider")
+                .with_children(Children::Layers(vec![track, knob])),
+        );
+        gui.attach_widget(
+            root,
+            Widget::Slider {
+                min: self.min,
+                max: self.max,
+                step: self.step,
+                value: snap(self.value, self.min, self.max, self.step),
+                fill,
+                knob,
+                on_change: self.on_change,
+            },
+        );
+        root
+    }
+}
+
+/// Shows progress between 0 and 1, change it with `Gui::set_widget_value`
+pub struct ProgressBar {
+    value: f32,
+}
+
+impl ProgressBar {
+    pub fn new(value: f32) -> Self {
+        Self { value }
+    }
+
+    pub fn build<Msg: Clone>(self, gui: &mut Gui<Msg>) -> ElementKey {
+        gui.default_widget_classes();
+        let fill = gui.add_element(Element::new().with_class("progress-fill"));
+        let root = gui.add_element(
+            Element::new()
+                .with_class("progress")
+                .with_children(Children::Element(fill)),
+        );
+        gui.attach_widget(
+            root,
+            Widget::Progress {
+                value: self.value.clamp(0.0, 1.0),
+                fill,
+            },
+        );
+        root
+    }
+}
+
+/// Button that opens a list of options of which one is selected
+///
+/// The list is shown as the root of `Layer::Popup` below the dropdown and scrolls
+/// when there are more options than rows.
+pub struct Dropdown<Msg> {
+    options: Vec<String>,
+    selected: usize,
+    max_rows: usize,
+    on_change: Option<fn(usize) -> Msg>,
+}
+
+impl<Msg: Clone> Dropdown<Msg> {
+    pub fn new(options: &[&str], selected: usize) -> Self {
+        Self {
+            options: options.iter().map(|o| o.to_string()).collect(),
+            selected,
+            max_rows: 8,
+            on_change: None,
+        }
+    }
+
+    /// Number of options shown at once, defaults to 8
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = max_rows;
+        self
+    }
+
+    /// Called with the index of the selected option
+    pub fn on_change(mut self, on_change: fn(usize) -> Msg) -> Self {
+        self.on_change = Some(on_change);
+        self
+    }
+
+    pub fn build(self, gui: &mut Gui<Msg>) -> ElementKey {
+        gui.default_widget_classes();
+        let mut element = Element::new().with_class("dropdown");
+        element.styles.selectable = true;
+        let root = gui.add_element(element);
+        let selected = self.selected.min(self.options.len().saturating_sub(1));
+        let mut rows = Rows::new(gui, "dropdown-item", self.options, self.max_rows);
+        rows.reveal(selected);
+        let mut list = Element::new()
+            .with_class("dropdown-list")
+            .with_children(rows.sections());
+        list.styles.position.set(
+            Position::bottom_left()
+                .relative_to(root)
+                .with_pivot(PositionValues::TopLeft),
+        );
+        list.styles.width.set(Values::container(100.0, Side::Width));
+        let height = 100.0 * rows.elements.len() as f32;
+        list.styles.height.set(Values::container(height, Side::Height));
+        list.widget = Some(Widget::DropdownList { dropdown: root });
+        let list = gui.add_element(list);
+        gui.attach_widget(
+            root,
+            Widget::Dropdown {
+                selected,
+                list,
+                rows,
+                on_change: self.on_change,
+            },
+        );
+        root
+    }
+}
+
+/// Scrollable list of options of which one or more are selected
+pub struct ListBox<Msg> {
+    options: Vec<String>,
+    selected: Vec<usize>,
+    multiple: bool,
+    max_rows: Option<usize>,
+    on_change: Option<fn(Vec<usize>) -> Msg>,
+}
+
+impl<Msg: Clone> ListBox<Msg> {
+    pub fn new(options: &[&str]) -> Self {
+        Self {
+            options: options.iter().map(|o| o.to_string()).collect(),
+            selected: Vec::new(),
+            multiple: false,
+            max_rows: None,
+            on_change: None,
+        }
+    }
+
+    /// Clicking an option toggles it instead of replacing the selection
+    pub fn multiple(mut self) -> Self {
+        self.multiple = true;
+        self
+    }
+
+    pub fn with_selected(mut self, selected: &[usize]) -> Self {
+        self.selected = selected.to_vec();
+        self
+    }
+
+    /// Number of options shown at once, all options are shown by default
+    pub fn with_max_rows(mut self, max_rows: usize) -> Self {
+        self.max_rows = Some(max_rows);
+        self
+    }
+
+    /// Called with the indices of the selected options
+    pub fn on_change(mut self, on_change: fn(Vec<usize>) -> Msg) -> Self {
+        self.on_change = Some(on_change);
+        self
+    }
+
+    pub fn build(self, gui: &mut Gui<Msg>) -> ElementKey {
+        gui.default_widget_classes();
+        let max_rows = self.max_rows.unwrap_or(self.options.len());
+        let rows = Rows::new(gui, "list-item", self.options, max_rows);
+        let root = gui.add_element(
+            Element::new()
+                .with_class("list-box")
+                .with_children(rows.sections()),
+        );
+        let mut widget = Widget::ListBox {
+            selected: Vec::new(),
+            multiple: self.multiple,
+            rows,
+            on_change: self.on_change,
+        };
+        widget.set_value(WidgetValue::Indices(self.selected));
+        gui.attach_widget(root, widget);
+        root
+    }
+}
+
+/// Items of a `VirtualList`
+pub trait ListSource<Msg: Clone> {
+    /// Number of items, read again by `Gui::refresh_list`
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Shows the item at `index` in `row`, called whenever the row is recycled
+    fn show_row(&self, gui: &mut Gui<Msg>, row: ElementKey, index: usize);
+
+    /// Creates the `Element` of a row, called once for every visible row
+    fn create_row(&self, gui: &mut Gui<Msg>) -> ElementKey {
+        gui.add_element(Element::new().with_class("virtual-list-row"))
+    }
+}
+
+/// `ListSource` of text rows
+struct TextSource<F> {
+    len: usize,
+    text: F,
+}
+
+impl<Msg: Clone, F: Fn(usize) -> String> ListSource<Msg> for TextSource<F> {
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn show_row(&self, gui: &mut Gui<Msg>, row: ElementKey, index: usize) {
+        if let Some(row) = gui.get_element_mut(row) {
+            row.set_text(Some((self.text)(index)));
+        }
+    }
+}
+
+/// List that only creates `Elements` for visible rows and recycles them while scrolling
+///
+/// Suited for lists with thousands of items, only `rows` `Elements` take part in
+/// the layout no matter the number of items.
+///
+/// ```ignore
+/// let log = VirtualList::text(lines.len(), move |i| lines[i].clone())
+///     .with_rows(20)
+///     .on_select(Msg::Line)
+///     .build(&mut gui);
+/// ```
+pub struct VirtualList<Msg: Clone> {
+    source: Box<dyn ListSource<Msg>>,
+    rows: usize,
+    on_select: Option<fn(usize) -> Msg>,
+}
+
+impl<Msg: Clone> VirtualList<Msg> {
+    pub fn new(source: impl ListSource<Msg> + 'static) -> Self {
+        Self {
+            source: Box::new(source),
+            rows: 10,
+            on_select: None,
+        }
+    }
+
+    /// List of `len` text rows
+    pub fn text(len: usize, text: impl Fn(usize) -> String + 'static) -> Self {
+        Self::new(TextSource { len, text })
+    }
+
+    /// Number of visible rows that share the height of the list, defaults to 10
+    pub fn with_rows(mut self, rows: usize) -> Self {
+        self.rows = rows.max(1);
+        self
+    }
+
+    /// Called with the index of a clicked item, the item gets the class `checked`
+    pub fn on_select(mut self, on_select: fn(usize) -> Msg) -> Self {
+        self.on_select = Some(on_select);
+        self
+    }
+
+    pub fn build(self, gui: &mut Gui<Msg>) -> ElementKey {
+        gui.default_widget_classes();
+        let rows = (0..self.rows)
+            .map(|_| self.source.create_row(gui))
+            .collect::<Vec<_>>();
+        let body = gui.add_element(Element::new().with_children(Children::Rows {
+            children: rows
+                .iter()
+                .map(|row| Section {
+                    element: *row,
+                    size: None,
+                })
+                .collect(),
+            spacing: None,
+        }));
+        let thumb = gui.add_element(Element::new().with_class("virtual-list-thumb"));
+        let root = gui.add_element(
+            Element::new()
+                .with_class("virtual-list")
+                .with_children(Children::Layers(vec![body, thumb])),
+        );
+        gui.attach_widget(
+            root,
+            Widget::VirtualList {
+                source: self.source,
+                scroll: 0,
+                rows,
+                thumb,
+                selected: None,
+                on_select: self.on_select,
+            },
+        );
+        root
+    }
+}
+
+/// Pages of which one is visible, switched by a strip of tabs
+pub struct TabView<Msg> {
+    tabs: Vec<(String, ElementKey)>,
+    selected: usize,
+    on_change: Option<fn(usize) -> Msg>,
+}
+
+impl<Msg: Clone> TabView<Msg> {
+    pub fn new() -> Self {
+        Self {
+            tabs: Vec::new(),
+            selected: 0,
+            on_change: None,
+        }
+    }
+
+    /// Adds a tab that shows `page`, hidden pages have `Styles::visible` set to false
+    pub fn with_tab(mut self, title: &str, page: ElementKey) -> Self {
+        self.tabs.push((title.to_string(), page));
+        self
+    }
+
+    pub fn with_selected(mut self, selected: usize) -> Self {
+        self.selected = selected;
+        self
+    }
+
+    /// Called with the index of the selected tab
+    pub fn on_change(mut self, on_change: fn(usize) -> Msg) -> Self {
+        self.on_change = Some(on_change);
+        self
+    }
+
+    pub fn build(self, gui: &mut Gui<Msg>) -> ElementKey {
+        gui.default_widget_classes();
+        let mut tabs = Vec::new();
+        let mut pages = Vec::new();
+        for (title, page) in self.tabs {
+            let mut tab = Element::new().with_class("tab");
+            tab.set_text(Some(title));
+            tabs.push(gui.add_element(tab));
+            pages.push(page);
+        }
+        let strip = gui.add_element(
+            Element::new()
+                .with_class("tab-strip")
+                .with_children(Children::Columns {
+                    children: tabs
+                        .iter()
+                        .map(|tab| Section {
+                            element: *tab,
+                            size: None,
+                        })
+                        .collect(),
+                    spacing: None,
+                }),
+        );
+        let body = gui.add_element(
+            Element::new()
+                .with_class("tab-body")
+                .with_children(Children::Layers(pages.clone())),
+        );
+        let root = gui.add_element(
+            Element::new()
+                .with_class("tab-view")
+                .with_children(Children::Rows {
+                    children: vec![
+                        Section {
+                            element: strip,
+                            size: Some(Values::logical(32.0)),
+                        },
+                        Section {
+                            element: body,
+                            size: None,
+                        },
+                    ],
+                    spacing: None,
+                }),
+        );
+        let mut widget = Widget::Tabs {
+            selected: 0,
+            tabs,
+            pages,
+            on_change: self.on_change,
+        };
+        widget.set_value(WidgetValue::Index(self.selected));
+        gui.attach_widget(root, widget);
+        root
+    }
+}
+
+impl<Msg: Clone> Default for TabView<Msg> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Two sections divided by a divider that can be dragged to resize them
+pub struct SplitPane<Msg> {
+    first: ElementKey,
+    second: ElementKey,
+    rows: bool,
+    ratio: f32,
+    on_change: Option<fn(f32) -> Msg>,
+}
+
+impl<Msg: Clone> SplitPane<Msg> {
+    /// `first` on the left and `second` on the right
+    pub fn columns(first: ElementKey, second: ElementKey) -> Self {
+        Self {
+            first,
+            second,
+            rows: false,
+            ratio: 0.5,
+            on_change: None,
+        }
+    }
+
+    /// `first` on the top and `second` on the bottom
+    pub fn rows(first: ElementKey, second: ElementKey) -> Self {
+        Self {
+            rows: true,
+            ..Self::columns(first, second)
+        }
+    }
+
+    /// Part of the pane taken by the first section, defaults to 0.5
+    pub fn with_ratio(mut self, ratio: f32) -> Self {
+        self.ratio = ratio;
+        self
+    }
+
+    /// Called with the new ratio while the divider is dragged
+    pub fn on_change(mut self, on_change: fn(f32) -> Msg) -> Self {
+        self.on_change = Some(on_change);
+        self
+    }
+
+    pub fn build(self, gui: &mut Gui<Msg>) -> ElementKey {
+        gui.default_widget_classes();
+        let divider = gui.add_element(Element::new().with_class("split-divider"));
+        let children = vec![
+            Section {
+                element: self.first,
+                size: None,
+            },
+            Section {
+                element: divider,
+                size: Some(Values::logical(6.0)),
+            },
+            Section {
+                element: self.second,
+                size: None,
+            },
+        ];
+        let children = match self.rows {
+            true => Children::Rows {
+                children,
+                spacing: None,
+            },
+            false => Children::Columns {
+                children,
+                spacing: None,
+            },
+        };
+        let root = gui.add_element(
+            Element::new()
+                .with_class("split-pane")
+                .with_children(children),
+        );
+        gui.attach_widget(
+            root,
+            Widget::Split {
+                rows: self.rows,
+                ratio: self.ratio.clamp(0.0, 1.0),
+                divider,
+                dragging: false,
+                on_change: self.on_change,
+            },
+        );
+        root
+    }
+}
+
+/// Header that shows or hides the content below it
+///
+/// The height of the content is animated with a transition, see `Collapsible::with_transition`
+pub struct Collapsible<Msg> {
+    title: String,
+    content: ElementKey,
+    expanded: bool,
+    transition: Option<Transition>,
+    on_change: Option<fn(bool) -> Msg>,
+}
+
+impl<Msg: Clone> Collapsible<Msg> {
+    pub fn new(title: &str, content: ElementKey) -> Self {
+        Self {
+            title: title.to_string(),
+            content,
+            expanded: true,
+            transition: Some(Transition::new(0.2, Easing::EaseOut)),
+            on_change: None,
+        }
+    }
+
+    pub fn with_expanded(mut self, expanded: bool) -> Self {
+        self.expanded = expanded;
+        self
+    }
+
+    /// Transition of the height of the content, `None` collapses it instantly
+    pub fn with_transition(mut self, transition: Option<Transition>) -> Self {
+        self.transition = transition;
+        self
+    }
+
+    pub fn on_change(mut self, on_change: fn(bool) -> Msg) -> Self {
+        self.on_change = Some(on_change);
+        self
+    }
+
+    pub fn build(self, gui: &mut Gui<Msg>) -> ElementKey {
+        gui.default_widget_classes();
+        let mut header = Element::new().with_class("collapsible-header");
+        header.set_text(Some(self.title));
+        let header = gui.add_element(header);
+        let mut body = Element::new()
+            .with_class("collapsible-body")
+            .with_children(Children::Element(self.content));
+        body.styles
+            .position
+            .set(Position::top().with_pivot(PositionValues::Top));
+        body.styles.transitions.set(Transitions {
+            height: self.transition,
+            ..Default::default()
+        });
+        let body = gui.add_element(body);
+        let root = gui.add_element(
+            Element::new()
+                .with_class("collapsible")
+                .with_children(Children::Rows {
+                    children: vec![
+                        Section {
+                            element: header,
+                            size: Some(Values::logical(28.0)),
+                        },
+                        Section {
+                            element: body,
+                            size: None,
+                        },
+                    ],
+                    spacing: None,
+                }),
+        );
+        gui.attach_widget(
+            root,
+            Widget::Collapsible {
+                expanded: self.expanded,
+                header,
+                body,
+                on_change: self.on_change,
+            },
+        );
+        root
+    }
+}
+
+/// Area with floating windows that can be docked into its sides
+///
+/// Windows are overlays of the desktop, see `FloatingWindow`.
+/// Dragging a window close to an edge of the desktop docks it into that half.
+pub struct Desktop {
+    content: Option<ElementKey>,
+}
+
+impl Desktop {
+    pub fn new() -> Self {
+        Self { content: None }
+    }
+
+    /// `Element` shown beneath the windows
+    pub fn with_content(mut self, content: ElementKey) -> Self {
+        self.content = Some(content);
+        self
+    }
+
+    pub fn build<Msg: Clone>(self, gui: &mut Gui<Msg>) -> ElementKey {
+        gui.default_widget_classes();
+        let mut preview = Element::new().with_class("dock-preview");
+        preview.styles.visible = false;
+        let preview = gui.add_element(preview);
+        let children = match self.content {
+            Some(content) => Children::Element(content),
+            None => Children::None,
+        };
+        let root = gui.add_element(
+            Element::new()
+                .with_class("desktop")
+                .with_children(children)
+                .with_overlay(preview),
+        );
+        gui.attach_widget(root, Widget::Desktop { preview, top: 0 });
+        root
+    }
+}
+
+impl Default for Desktop {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Window with a title bar that can be moved, resized, minimized and closed
+///
+/// The value of a window is `WidgetValue::Bool` telling whether it is open,
+/// closed windows are hidden and can be opened with `Gui::set_widget_value`.
+pub struct FloatingWindow<Msg> {
+    title: String,
+    content: ElementKey,
+    rect: [f32; 4],
+    closable: bool,
+    minimizable: bool,
+    resizable: bool,
+    dock: Option<Dock>,
+    on_close: Option<Msg>,
+}
+
+impl<Msg: Clone> FloatingWindow<Msg> {
+    pub fn new(title: &str, content: ElementKey) -> Self {
+        Self {
+            title: title.to_string(),
+            content,
+            rect: [40.0, 40.0, 320.0, 240.0],
+            closable: true,
+            minimizable: true,
+            resizable: true,
+            dock: None,
+            on_close: None,
+        }
+    }
+
+    /// Position of the top left corner relative to the desktop in pixels
+    pub fn with_position(mut self, x: f32, y: f32) -> Self {
+        self.rect[0] = x;
+        self.rect[1] = y;
+        self
+    }
+
+    /// Size in pixels
+    pub fn with_size(mut self, width: f32, height: f32) -> Self {
+        self.rect[2] = width;
+        self.rect[3] = height;
+        self
+    }
+
+    pub fn closable(mut self, closable: bool) -> Self {
+        self.closable = closable;
+        self
+    }
+
+    pub fn minimizable(mut self, minimizable: bool) -> Self {
+        self.minimizable = minimizable;
+        self
+    }
+
+    pub fn resizable(mut self, resizable: bool) -> Self {
+        self.resizable = resizable;
+        self
+    }
+
+    pub fn with_dock(mut self, dock: Dock) -> Self {
+        self.dock = Some(dock);
+        self
+    }
+
+    /// Message emitted with `EventTypes::Change` when the close button is clicked
+    pub fn on_close(mut self, msg: Msg) -> Self {
+        self.on_close = Some(msg);
+        self
+    }
+
+    /// Adds the window to `desktop` and raises it above the other windows
+    pub fn build(self, gui: &mut Gui<Msg>, desktop: ElementKey) -> ElementKey {
+        gui.default_widget_classes();
+        let button = |gui: &mut Gui<Msg>, text: &str| {
+            let mut element = Element::new().with_class("window-button");
+            element.set_text(Some(text.to_string()));
+            gui.add_element(element)
+        };
+        let minimize = self.minimizable.then(|| button(gui, "_"));
+        let close = self.closable.then(|| button(gui, "x"));
+        let mut title = Element::new().with_class("window-title-text");
+        title.set_text(Some(self.title));
+        let mut sections = vec![Section {
+            element: gui.add_element(title),
+            size: None,
+        }];
+        for button in minimize.iter().chain(close.iter()) {
+            sections.push(Section {
+                element: *button,
+                size: Some(Values::container(100.0, Side::Height)),
+            });
+        }
+        let title_bar = gui.add_element(
+            Element::new()
+                .with_class("window-title")
+                .with_children(Children::Columns {
+                    children: sections,
+                    spacing: None,
+                }),
+        );
+        let body = gui.add_element(
+            Element::new()
+                .with_class("window-body")
+                .with_children(Children::Element(self.content)),
+        );
+        let mut root = Element::new()
+            .with_class("window")
+            .with_children(Children::Rows {
+                children: vec![
+                    Section {
+                        element: title_bar,
+                        size: Some(Values::logical(TITLE_HEIGHT)),
+                    },
+                    Section {
+                        element: body,
+                        size: None,
+                    },
+                ],
+                spacing: None,
+            });
+        let grip = match self.resizable {
+            true => {
+                let mut grip = Element::new().with_class("window-grip");
+                grip.styles.position.set(
+                    Position::bottom_right().with_pivot(PositionValues::BottomRight),
+                );
+                let grip = gui.add_element(grip);
+                root = root.with_overlay(grip);
+                Some(grip)
+            }
+            false => None,
+        };
+        let root = gui.add_element(root);
+        if let Some(desktop) = gui.get_element_mut(desktop) {
+            desktop.overlays.push(root);
+        }
+        gui.attach_widget(
+            root,
+            Widget::Window(Box::new(WindowState {
+                desktop,
+                title_bar,
+                body,
+                minimize,
+                close,
+                grip,
+                rect: self.rect,
+                open: true,
+                minimized: false,
+                dock: self.dock,
+                drag: WindowDrag::None,
+                on_close: self.on_close,
+            })),
+        );
+        gui.raise_window(root);
+        root
+    }
+}
+
+/// Places `control` in a square next to an optional label
+fn labeled<Msg: Clone>(
+    gui: &mut Gui<Msg>,
+    class: &str,
+    control: ElementKey,
+    label: Option<String>,
+) -> ElementKey {
+    let mut children = vec![Section {
+        element: control,
+        size: Some(Values::container(100.0, Side::Height)),
+    }];
+    if let Some(label) = label {
+        let mut element = Element::new().with_class(&format!("{}-label", class));
+        element.set_text(Some(label));
+        children.push(Section {
+            element: gui.add_element(element),
+            size: None,
+        });
+    }
+    gui.add_element(
+        Element::new()
+            .with_class(class)
+            .with_children(Children::Columns {
+                children,
+                spacing: None,
+            }),
+    )
+}
+
+impl<Msg: Clone> Gui<Msg> {
+    /// Returns the value of a widget
+    pub fn widget_value(&self, key: ElementKey) -> Option<WidgetValue> {
+        self.get_element(key)?.widget.as_ref()?.value()
+    }
+
+    /// Changes the value of a widget without emitting a change event
+    ///
+    /// Values of the wrong type are ignored
+    pub fn set_widget_value(&mut self, key: ElementKey, value: WidgetValue) {
+        let mut widget = match self.get_element_mut(key).and_then(|e| e.widget.take()) {
+            Some(widget) => widget,
+            None => return,
+        };
+        if widget.set_value(value) {
+            self.show_widget(key, &widget);
+        }
+        if let Some(element) = self.get_element_mut(key) {
+            element.widget = Some(widget);
+        }
+    }
+
+    /// Reads the number of items of a `VirtualList` again and shows its rows
+    ///
+    /// Call after the data of the `ListSource` changes
+    pub fn refresh_list(&mut self, key: ElementKey) {
+        self.scroll_list(key, |scroll, _| scroll);
+    }
+
+    /// Scrolls a `VirtualList` so that the item at `index` is visible
+    pub fn scroll_list_to(&mut self, key: ElementKey, index: usize) {
+        self.scroll_list(key, |scroll, rows| match index {
+            index if index < scroll => index,
+            index if index >= scroll + rows => index + 1 - rows,
+            _ => scroll,
+        });
+    }
+
+    /// Moves a `FloatingWindow` above the other windows of its desktop
+    ///
+    /// The `z_index` of the window and all of its `Elements` is raised by the same amount
+    pub fn raise_window(&mut self, key: ElementKey) {
+        let (desktop, z_index) = match self.get_element(key) {
+            Some(element) => match &element.widget {
+                Some(Widget::Window(window)) => (window.desktop, element.styles.z_index),
+                _ => return,
+            },
+            None => return,
+        };
+        let top = match self.get_element_mut(desktop).and_then(|e| e.widget.as_mut()) {
+            Some(Widget::Desktop { top, .. }) => top,
+            _ => return,
+        };
+        if z_index == *top && z_index > 0 {
+            return;
+        }
+        *top += 1;
+        let delta = *top - z_index;
+        self.traverse_elements_mut(key, &mut |element| element.styles.z_index += delta);
+    }
+
+    /// Docks a `FloatingWindow` into a region of its desktop, `None` makes it float again
+    pub fn dock_window(&mut self, key: ElementKey, dock: Option<Dock>) {
+        self.update_window(key, |window| window.dock = dock);
+    }
+
+    pub fn minimize_window(&mut self, key: ElementKey, minimized: bool) {
+        self.update_window(key, |window| window.minimized = minimized);
+    }
+
+    fn update_window(&mut self, key: ElementKey, f: impl FnOnce(&mut WindowState<Msg>)) {
+        let mut widget = match self.get_element_mut(key).and_then(|e| e.widget.take()) {
+            Some(widget) => widget,
+            None => return,
+        };
+        if let Widget::Window(window) = &mut widget {
+            f(window);
+            self.show_widget(key, &widget);
+        }
+        if let Some(element) = self.get_element_mut(key) {
+            element.widget = Some(widget);
+        }
+    }
+
+    /// Sets the first shown item of a `VirtualList` to `f(scroll, rows)`
+    fn scroll_list(&mut self, key: ElementKey, f: impl FnOnce(usize, usize) -> usize) {
+        let mut widget = match self.get_element_mut(key).and_then(|e| e.widget.take()) {
+            Some(widget) => widget,
+            None => return,
+        };
+        if let Widget::VirtualList {
+            source,
+            scroll,
+            rows,
+            selected,
+            ..
+        } = &mut widget
+        {
+            let len = source.len();
+            *scroll = f(*scroll, rows.len()).min(len.saturating_sub(rows.len()));
+            *selected = selected.filter(|selected| *selected < len);
+        }
+        self.show_widget(key, &widget);
+        if let Some(element) = self.get_element_mut(key) {
+            element.widget = Some(widget);
+        }
+    }
+
+    fn attach_widget(&mut self, key: ElementKey, widget: Widget<Msg>) {
+        self.show_widget(key, &widget);
+        if let Some(rows) = widget.rows() {
+            for row in &rows.elements {
+                self.row_owners.insert(*row, key);
+            }
+        }
+        if let Some(element) = self.get_element_mut(key) {
+            element.widget = Some(widget);
+        }
+    }
+
+    /// Reacts to mouse input, called before the event is dispatched to listeners
+    pub(crate) fn widget_event(&mut self, event: &WindowEvent) {
+        match event {
+            WindowEvent::MouseDown { .. } => {
+                let window = self.find_under_cursor(|e| matches!(e.widget, Some(Widget::Window(_))));
+                if let Some(window) = window {
+                    self.raise_window(window);
+                }
+                self.active_widget = self.find_under_cursor(|e| e.widget.is_some());
+                if let Some(open) = self.open_dropdown {
+                    let list = match self.get_element(open).and_then(|e| e.widget.as_ref()) {
+                        Some(Widget::Dropdown { list, .. }) => Some(*list),
+                        _ => None,
+                    };
+                    if self.active_widget != Some(open) && self.active_widget != list {
+                        self.close_dropdown();
+                    }
+                }
+                if let Some(key) = self.active_widget {
+                    self.drag_widget(key, event);
+                }
+            }
+            WindowEvent::MouseMove { .. } => {
+                if let Some(key) = self.active_widget {
+                    self.drag_widget(key, event);
+                }
+            }
+            WindowEvent::MouseUp { .. } => {
+                let key = match self.active_widget.take() {
+                    Some(key) => key,
+                    None => return,
+                };
+                self.release_widget(key);
+                if self.find_under_cursor(|e| e.widget.is_some()) == Some(key) {
+                    self.click_widget(key, event);
+                }
+            }
+            WindowEvent::Scroll { delta } => {
+                if let Some(key) = self.find_under_cursor(|e| e.widget.is_some()) {
+                    self.scroll_widget(key, delta.y);
+                }
+            }
+            WindowEvent::Input { text } if text == "\r" || text == " " => {
+                let selected = match self.select.selected {
+                    Some(selected) => selected,
+                    None => return,
+                };
+                let is_dropdown = matches!(
+                    self.get_element(selected).and_then(|e| e.widget.as_ref()),
+                    Some(Widget::Dropdown { .. })
+                );
+                if is_dropdown && text == "\r" {
+                    self.toggle_dropdown(selected);
+                } else if let Some((owner, index)) = self.row_owner(selected) {
+                    self.pick_row(owner, index, event);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    /// Finds the widget showing `key` as one of its rows and the index of the shown option
+    fn row_owner(&self, key: ElementKey) -> Option<(ElementKey, usize)> {
+        let owner = *self.row_owners.get(&key)?;
+        let index = self.get_element(owner)?.widget.as_ref()?.rows()?.index_of(key)?;
+        Some((owner, index))
+    }
+
+    /// Index of the option under the cursor
+    fn row_under_cursor(&self, key: ElementKey) -> Option<usize> {
+        let rows = self.get_element(key)?.widget.as_ref()?.rows()?;
+        let mouse = self.input.mouse;
+        let row = rows.elements.iter().find(|row| {
+            self.get_element(**row)
+                .map(|e| e.transform.point_collision(mouse))
+                .unwrap_or(false)
+        })?;
+        rows.index_of(*row)
+    }
+
+    /// Selects an option of a dropdown or a list box
+    fn pick_row(&mut self, key: ElementKey, index: usize, event: &WindowEvent) {
+        let mut widget = match self.get_element_mut(key).and_then(|e| e.widget.take()) {
+            Some(widget) => widget,
+            None => return,
+        };
+        let mut close = false;
+        match &mut widget {
+            Widget::Dropdown {
+                selected,
+                on_change,
+                ..
+            } => {
+                close = true;
+                if index != *selected {
+                    *selected = index;
+                    if let Some(on_change) = on_change {
+                        let msg = on_change(index);
+                        self.emit_change(key, event, WidgetValue::Index(index), msg);
+                    }
+                }
+            }
+            Widget::ListBox {
+                selected,
+                multiple,
+                on_change,
+                ..
+            } => {
+                let changed = match (*multiple, selected.binary_search(&index)) {
+                    (true, Ok(i)) => {
+                        selected.remove(i);
+                        true
+                    }
+                    (true, Err(i)) => {
+                        selected.insert(i, index);
+                        true
+                    }
+                    (false, Ok(_)) => false,
+                    (false, Err(_)) => {
+                        *selected = vec![index];
+                        true
+                    }
+                };
+                if let (true, Some(on_change)) = (changed, on_change) {
+                    let msg = on_change(selected.clone());
+                    self.emit_change(key, event, WidgetValue::Indices(selected.clone()), msg);
+                }
+            }
+            _ => (),
+        }
+        self.show_widget(key, &widget);
+        if let Some(element) = self.get_element_mut(key) {
+            element.widget = Some(widget);
+        }
+        if close {
+            self.close_dropdown();
+        }
+    }
+
+    /// Selects the item of a `VirtualList` under the cursor
+    fn select_list_item(&mut self, key: ElementKey, event: &WindowEvent) {
+        let mut widget = match self.get_element_mut(key).and_then(|e| e.widget.take()) {
+            Some(widget) => widget,
+            None => return,
+        };
+        if let Widget::VirtualList {
+            source,
+            scroll,
+            rows,
+            selected,
+            on_select,
+            ..
+        } = &mut widget
+        {
+            let mouse = self.input.mouse;
+            let index = rows
+                .iter()
+                .position(|row| {
+                    self.get_element(*row)
+                        .map(|e| e.transform.point_collision(mouse))
+                        .unwrap_or(false)
+                })
+                .map(|row| *scroll + row)
+                .filter(|index| *index < source.len());
+            if let Some(index) = index {
+                *selected = Some(index);
+                if let Some(on_select) = on_select {
+                    let msg = on_select(index);
+                    self.emit_change(key, event, WidgetValue::Index(index), msg);
+                }
+            }
+        }
+        self.show_widget(key, &widget);
+        if let Some(element) = self.get_element_mut(key) {
+            element.widget = Some(widget);
+        }
+    }
+
+    /// Opens the list of a dropdown or closes it if it is open
+    fn toggle_dropdown(&mut self, key: ElementKey) {
+        if self.open_dropdown == Some(key) {
+            self.close_dropdown();
+            return;
+        }
+        self.close_dropdown();
+        self.close_menus();
+        let mut widget = match self.get_element_mut(key).and_then(|e| e.widget.take()) {
+            Some(widget) => widget,
+            None => return,
+        };
+        if let Widget::Dropdown {
+            selected,
+            list,
+            rows,
+            ..
+        } = &mut widget
+        {
+            rows.reveal(*selected);
+            self.set_layer(Layer::Popup, Some(*list));
+            self.open_dropdown = Some(key);
+        }
+        self.show_widget(key, &widget);
+        if let Some(element) = self.get_element_mut(key) {
+            element.widget = Some(widget);
+        }
+    }
+
+    /// Hides the list of the open dropdown
+    pub(crate) fn close_dropdown(&mut self) {
+        let key = match self.open_dropdown.take() {
+            Some(key) => key,
+            None => return,
+        };
+        let (list, rows) = match self.get_element(key).and_then(|e| e.widget.as_ref()) {
+            Some(Widget::Dropdown { list, rows, .. }) => (*list, rows),
+            _ => return,
+        };
+        let in_list = match self.select.selected {
+            Some(selected) => rows.index_of(selected).is_some(),
+            None => false,
+        };
+        if in_list {
+            // keep the keyboard selection on the dropdown
+            self.select.selected = Some(key);
+        }
+        if self.layer(Layer::Popup) == Some(list) {
+            self.set_layer(Layer::Popup, None);
+        }
+    }
+
+    /// Scrolls the rows of a dropdown or a list box
+    fn scroll_widget(&mut self, key: ElementKey, delta: f32) {
+        let key = match self.get_element(key).and_then(|e| e.widget.as_ref()) {
+            Some(Widget::DropdownList { dropdown }) => *dropdown,
+            _ => key,
+        };
+        let mut widget = match self.get_element_mut(key).and_then(|e| e.widget.take()) {
+            Some(widget) => widget,
+            None => return,
+        };
+        if let Widget::Dropdown { rows, .. } | Widget::ListBox { rows, .. } = &mut widget {
+            if rows.scroll(delta) {
+                self.show_widget(key, &widget);
+            }
+        }
+        let is_list = matches!(widget, Widget::VirtualList { .. });
+        if let Some(element) = self.get_element_mut(key) {
+            element.widget = Some(widget);
+        }
+        if is_list {
+            // one row per line, at least one row per event
+            let lines = match delta.round() as isize {
+                0 => delta.signum() as isize,
+                lines => lines,
+            };
+            self.scroll_list(key, |scroll, _| scroll.saturating_add_signed(-lines));
+        }
+    }
+
+    /// Moves the knob of a slider to the cursor
+    fn drag_widget(&mut self, key: ElementKey, event: &WindowEvent) {
+        let (transform, mut widget) = match self.get_element_mut(key) {
+            Some(element) => match element.widget.take() {
+                Some(widget) => (element.transform.clone(), widget),
+                None => return,
+            },
+            None => return,
+        };
+        // position of the cursor along the width or the height in range `0..=1`
+        let along = |vertical: bool| {
+            let (center, size, mouse) = match vertical {
+                false => (transform.position.x, transform.scale.x, self.input.mouse.x),
+                true => (transform.position.y, transform.scale.y, self.input.mouse.y),
+            };
+            match size {
+                size if size > 0.0 => ((mouse - center + size / 2.0) / size).clamp(0.0, 1.0),
+                _ => 0.0,
+            }
+        };
+        match &mut widget {
+            Widget::Slider {
+                min,
+                max,
+                step,
+                value,
+                on_change,
+                ..
+            } => {
+                let t = along(false);
+                let new = snap(*min + t * (*max - *min), *min, *max, *step);
+                if new != *value {
+                    *value = new;
+                    if let Some(on_change) = on_change {
+                        let msg = on_change(new);
+                        self.emit_change(key, event, WidgetValue::Number(new), msg);
+                    }
+                    self.show_widget(key, &widget);
+                }
+            }
+            Widget::Split {
+                rows,
+                ratio,
+                divider,
+                dragging,
+                on_change,
+            } => {
+                if let WindowEvent::MouseDown { .. } = event {
+                    *dragging = self
+                        .get_element(*divider)
+                        .map(|e| e.transform.point_collision(self.input.mouse))
+                        .unwrap_or(false);
+                }
+                let new = along(*rows);
+                if *dragging && new != *ratio {
+                    *ratio = new;
+                    if let Some(on_change) = on_change {
+                        let msg = on_change(new);
+                        self.emit_change(key, event, WidgetValue::Number(new), msg);
+                    }
+                    self.show_widget(key, &widget);
+                }
+            }
+            Widget::Window(window) => {
+                self.drag_window(window, &transform, event);
+                self.show_widget(key, &widget);
+            }
+            _ => (),
+        }
+        if let Some(element) = self.get_element_mut(key) {
+            element.widget = Some(widget);
+        }
+    }
+
+    /// Moves or resizes a window, starts the drag on `WindowEvent::MouseDown`
+    fn drag_window(
+        &mut self,
+        window: &mut WindowState<Msg>,
+        transform: &ElementTransform,
+        event: &WindowEvent,
+    ) {
+        use WindowDrag::{Move, Resize};
+        let mouse = self.input.mouse;
+        let hit = |gui: &Self, key: Option<ElementKey>| {
+            key.and_then(|key| gui.get_element(key))
+                .map(|e| e.transform.point_collision(mouse))
+                .unwrap_or(false)
+        };
+        let desktop = match self.get_element(window.desktop) {
+            Some(desktop) => desktop.transform.clone(),
+            None => return,
+        };
+        let origin = Point::new(
+            desktop.position.x - desktop.scale.x / 2.0,
+            desktop.position.y - desktop.scale.y / 2.0,
+        );
+        if let WindowEvent::MouseDown { .. } = event {
+            let corner = Point::new(
+                transform.position.x - transform.scale.x / 2.0,
+                transform.position.y - transform.scale.y / 2.0,
+            );
+            window.drag = if hit(self, window.grip) && window.dock.is_none() {
+                Resize(Point::new(mouse.x - window.rect[2], mouse.y - window.rect[3]))
+            } else if hit(self, Some(window.title_bar))
+                && !hit(self, window.close)
+                && !hit(self, window.minimize)
+            {
+                Move(Point::new(mouse.x - corner.x, mouse.y - corner.y))
+            } else {
+                WindowDrag::None
+            };
+            return;
+        }
+        match window.drag {
+            Move(mut grab) => {
+                if window.dock.take().is_some() {
+                    // keep the cursor on the title bar of the restored window
+                    grab = Point::new(
+                        (grab.x / transform.scale.x.max(1.0)) * window.rect[2],
+                        grab.y,
+                    );
+                    window.drag = Move(grab);
+                }
+                window.rect[0] = mouse.x - origin.x - grab.x;
+                window.rect[1] = mouse.y - origin.y - grab.y;
+                let zone = self.dock_zone(&desktop, mouse);
+                self.show_dock_preview(window.desktop, zone);
+            }
+            Resize(grab) => {
+                let min = TITLE_HEIGHT * 2.0 * self.scale_factor;
+                window.rect[2] = (mouse.x - grab.x).max(min * 2.0);
+                window.rect[3] = (mouse.y - grab.y).max(min);
+            }
+            WindowDrag::None => (),
+        }
+    }
+
+    /// Region of the desktop docked into when a window is dropped at `point`
+    fn dock_zone(&self, desktop: &ElementTransform, point: Point) -> Option<Dock> {
+        let edge = 24.0 * self.scale_factor;
+        let left = desktop.position.x - desktop.scale.x / 2.0;
+        let top = desktop.position.y - desktop.scale.y / 2.0;
+        if !desktop.point_collision(point) {
+            return None;
+        }
+        if point.x - left < edge {
+            Some(Dock::Left)
+        } else if left + desktop.scale.x - point.x < edge {
+            Some(Dock::Right)
+        } else if point.y - top < edge {
+            Some(Dock::Top)
+        } else if top + desktop.scale.y - point.y < edge {
+            Some(Dock::Bottom)
+        } else {
+            None
+        }
+    }
+
+    fn show_dock_preview(&mut self, desktop: ElementKey, zone: Option<Dock>) {
+        let (preview, top) = match self.get_element(desktop).and_then(|e| e.widget.as_ref()) {
+            Some(Widget::Desktop { preview, top }) => (*preview, *top),
+            _ => return,
+        };
+        if let Some(preview) = self.get_element_mut(preview) {
+            preview.styles.visible = zone.is_some();
+            if let Some(zone) = zone {
+                let (position, width, height) = zone.place();
+                preview.styles.position.set(position);
+                preview.styles.width.set(width);
+                preview.styles.height.set(height);
+                preview.styles.z_index = top + 1;
+            }
+        }
+    }
+
+    /// Ends dragging of the widget that was pressed
+    fn release_widget(&mut self, key: ElementKey) {
+        let mut widget = match self.get_element_mut(key).and_then(|e| e.widget.take()) {
+            Some(widget) => widget,
+            None => return,
+        };
+        match &mut widget {
+            Widget::Split { dragging, .. } => *dragging = false,
+            Widget::Window(window) => {
+                if let WindowDrag::Move(_) = window.drag {
+                    let zone = self
+                        .get_element(window.desktop)
+                        .and_then(|desktop| self.dock_zone(&desktop.transform, self.input.mouse));
+                    window.dock = zone.or(window.dock);
+                    self.show_dock_preview(window.desktop, None);
+                }
+                window.drag = WindowDrag::None;
+                self.show_widget(key, &widget);
+            }
+            _ => (),
+        }
+        if let Some(element) = self.get_element_mut(key) {
+            element.widget = Some(widget);
+        }
+    }
+
+    fn click_widget(&mut self, key: ElementKey, event: &WindowEvent) {
+        match self.get_element(key).and_then(|e| e.widget.as_ref()) {
+            Some(Widget::Dropdown { .. }) => return self.toggle_dropdown(key),
+            Some(Widget::DropdownList { dropdown }) => {
+                let dropdown = *dropdown;
+                if let Some(index) = self.row_under_cursor(dropdown) {
+                    self.pick_row(dropdown, index, event);
+                }
+                return;
+            }
+            Some(Widget::ListBox { .. }) => {
+                if let Some(index) = self.row_under_cursor(key) {
+                    self.pick_row(key, index, event);
+                }
+                return;
+            }
+            Some(Widget::VirtualList { .. }) => return self.select_list_item(key, event),
+            _ => (),
+        }
+        let mut widget = match self.get_element_mut(key).and_then(|e| e.widget.take()) {
+            Some(widget) => widget,
+            None => return,
+        };
+        match &mut widget {
+            Widget::Button { on_click } => {
+                if let Some(msg) = on_click {
+                    self.events.events.push(Event {
+                        event_type: EventTypes::Click,
+                        window_event: Some(event.clone()),
+                        element_event: ElementEvent::Click,
+                        msg: msg.clone(),
+                        key,
+                    });
+                }
+            }
+            Widget::Checkbox {
+                checked: value,
+                on_change,
+                ..
+            }
+            | Widget::Toggle {
+                on: value,
+                on_change,
+                ..
+            } => {
+                *value = !*value;
+                if let Some(on_change) = on_change {
+                    let msg = on_change(*value);
+                    self.emit_change(key, event, WidgetValue::Bool(*value), msg);
+                }
+            }
+            Widget::Radio {
+                selected,
+                options,
+                on_change,
+            } => {
+                let mouse = self.input.mouse;
+                let clicked = options.iter().position(|option| {
+                    self.get_element(option.option)
+                        .map(|e| e.transform.point_collision(mouse))
+                        .unwrap_or(false)
+                });
+                if let Some(clicked) = clicked.filter(|clicked| clicked != selected) {
+                    *selected = clicked;
+                    if let Some(on_change) = on_change {
+                        let msg = on_change(clicked);
+                        self.emit_change(key, event, WidgetValue::Index(clicked), msg);
+                    }
+                }
+            }
+            Widget::Tabs {
+                selected,
+                tabs,
+                on_change,
+                ..
+            } => {
+                let mouse = self.input.mouse;
+                let clicked = tabs.iter().position(|tab| {
+                    self.get_element(*tab)
+                        .map(|e| e.transform.point_collision(mouse))
+                        .unwrap_or(false)
+                });
+                if let Some(clicked) = clicked.filter(|clicked| clicked != selected) {
+                    *selected = clicked;
+                    if let Some(on_change) = on_change {
+                        let msg = on_change(clicked);
+                        self.emit_change(key, event, WidgetValue::Index(clicked), msg);
+                    }
+                }
+            }
+            Widget::Window(window) => {
+                let mouse = self.input.mouse;
+                let hit = |key: Option<ElementKey>| {
+                    key.and_then(|key| self.get_element(key))
+                        .map(|e| e.transform.point_collision(mouse))
+                        .unwrap_or(false)
+                };
+                if hit(window.close) {
+                    window.open = false;
+                    if let Some(msg) = window.on_close.clone() {
+                        self.emit_change(key, event, WidgetValue::Bool(false), msg);
+                    }
+                } else if hit(window.minimize) {
+                    window.minimized = !window.minimized;
+                }
+            }
+            Widget::Collapsible {
+                expanded,
+                header,
+                on_change,
+                ..
+            } => {
+                let on_header = self
+                    .get_element(*header)
+                    .map(|e| e.transform.point_collision(self.input.mouse))
+                    .unwrap_or(false);
+                if on_header {
+                    *expanded = !*expanded;
+                    if let Some(on_change) = on_change {
+                        let msg = on_change(*expanded);
+                        self.emit_change(key, event, WidgetValue::Bool(*expanded), msg);
+                    }
+                }
+            }
+            Widget::Slider { .. }
+            | Widget::Progress { .. }
+            | Widget::Dropdown { .. }
+            | Widget::DropdownList { .. }
+            | Widget::ListBox { .. }
+            | Widget::VirtualList { .. }
+            | Widget::Split { .. }
+            | Widget::Desktop { .. } => (),
+        }
+        self.show_widget(key, &widget);
+        if let Some(element) = self.get_element_mut(key) {
+            element.widget = Some(widget);
+        }
+    }
+
+    fn emit_change(&mut self, key: ElementKey, event: &WindowEvent, value: WidgetValue, msg: Msg) {
+        self.events.events.push(Event {
+            event_type: EventTypes::Change,
+            window_event: Some(event.clone()),
+            element_event: ElementEvent::Change { value },
+            msg,
+            key,
+        });
+    }
+
+    /// Updates the `Elements` of a widget to show its value
+    fn show_widget(&mut self, key: ElementKey, widget: &Widget<Msg>) {
+        let checked = |gui: &mut Self, key: ElementKey, checked: bool| {
+            if let Some(element) = gui.get_element_mut(key) {
+                match checked {
+                    true => element.add_class("checked"),
+                    false => element.remove_class("checked"),
+                }
+            }
+        };
+        match widget {
+            Widget::Button { .. } => (),
+            Widget::Checkbox {
+                checked: on,
+                control,
+                mark,
+                ..
+            } => {
+                checked(self, *control, *on);
+                if let Some(mark) = self.get_element_mut(*mark) {
+                    mark.styles.visible = *on;
+                }
+            }
+            Widget::Toggle {
+                on, control, knob, ..
+            } => {
+                checked(self, *control, *on);
+                if let Some(knob) = self.get_element_mut(*knob) {
+                    let side = match on {
+                        true => PositionValues::CenterRight,
+                        false => PositionValues::CenterLeft,
+                    };
+                    knob.styles.position.set(Position::new(side).with_pivot(side));
+                }
+            }
+            Widget::Radio {
+                selected, options, ..
+            } => {
+                for (i, RadioOption { control, dot, .. }) in options.iter().enumerate() {
+                    checked(self, *control, i == *selected);
+                    if let Some(dot) = self.get_element_mut(*dot) {
+                        dot.styles.visible = i == *selected;
+                    }
+                }
+            }
+            Widget::Slider {
+                min,
+                max,
+                value,
+                fill,
+                knob,
+                ..
+            } => {
+                let t = fraction(*value, *min, *max);
+                if let Some(fill) = self.get_element_mut(*fill) {
+                    fill.styles.width.set(Values::container(t * 100.0, Side::Width));
+                }
+                if let Some(knob) = self.get_element_mut(*knob) {
+                    knob.styles.position.set(
+                        Position::fraction(t, 0.5).with_pivot(PositionValues::Fraction(t, 0.5)),
+                    );
+                }
+            }
+            Widget::Progress { value, fill } => {
+                if let Some(fill) = self.get_element_mut(*fill) {
+                    fill.styles.width.set(Values::container(value * 100.0, Side::Width));
+                }
+            }
+            Widget::Dropdown { selected, rows, .. } => {
+                if let Some(element) = self.get_element_mut(key) {
+                    element.set_text(rows.options.get(*selected).cloned());
+                }
+                rows.show(self, |i| i == *selected);
+            }
+            Widget::DropdownList { .. } => (),
+            Widget::ListBox { selected, rows, .. } => {
+                rows.show(self, |i| selected.binary_search(&i).is_ok());
+            }
+            Widget::VirtualList {
+                source,
+                scroll,
+                rows,
+                thumb,
+                selected,
+                ..
+            } => {
+                let len = source.len();
+                for (row, key) in rows.iter().enumerate() {
+                    let index = *scroll + row;
+                    if index < len {
+                        source.show_row(self, *key, index);
+                    }
+                    if let Some(element) = self.get_element_mut(*key) {
+                        element.styles.visible = index < len;
+                        match *selected == Some(index) {
+                            true => element.add_class("checked"),
+                            false => element.remove_class("checked"),
+                        }
+                    }
+                }
+                if let Some(thumb) = self.get_element_mut(*thumb) {
+                    thumb.styles.visible = len > rows.len();
+                    let t = fraction(*scroll as f32, 0.0, len.saturating_sub(rows.len()) as f32);
+                    let height = rows.len() as f32 / len.max(1) as f32 * 100.0;
+                    thumb.styles.height.set(Values::container(height, Side::Height));
+                    thumb.styles.position.set(
+                        Position::fraction(1.0, t).with_pivot(PositionValues::Fraction(1.0, t)),
+                    );
+                }
+            }
+            Widget::Tabs {
+                selected,
+                tabs,
+                pages,
+                ..
+            } => {
+                for (i, (tab, page)) in tabs.iter().zip(pages).enumerate() {
+                    checked(self, *tab, i == *selected);
+                    if let Some(page) = self.get_element_mut(*page) {
+                        page.styles.visible = i == *selected;
+                    }
+                }
+            }
+            Widget::Split { rows, ratio, .. } => {
+                let side = match rows {
+                    true => Side::Height,
+                    false => Side::Width,
+                };
+                if let Some(element) = self.get_element_mut(key) {
+                    if let Children::Rows { children, .. } | Children::Columns { children, .. } =
+                        &mut element.children
+                    {
+                        if let Some(first) = children.first_mut() {
+                            first.size = Some(Values::container(ratio * 100.0, side));
+                        }
+                    }
+                }
+            }
+            Widget::Desktop { .. } => (),
+            Widget::Window(window) => {
+                if let Some(minimize) = window.minimize {
+                    checked(self, minimize, window.minimized);
+                }
+                if let Some(body) = self.get_element_mut(window.body) {
+                    body.styles.visible = !window.minimized;
+                }
+                if let Some(grip) = window.grip.and_then(|grip| self.get_element_mut(grip)) {
+                    grip.styles.visible = !window.minimized && window.dock.is_none();
+                }
+                let [x, y, width, height] = window.rect;
+                let (position, width, height) = match window.dock {
+                    Some(dock) => dock.place(),
+                    None => (
+                        Position::top_left()
+                            .with_pivot(PositionValues::TopLeft)
+                            .with_offset(x, y),
+                        Values::px(width),
+                        Values::px(height),
+                    ),
+                };
+                if let Some(element) = self.get_element_mut(key) {
+                    element.styles.visible = window.open;
+                    element.styles.position.set(position);
+                    element.styles.width.set(width);
+                    element.styles.height.set(match window.minimized {
+                        true => Values::logical(TITLE_HEIGHT),
+                        false => height,
+                    });
+                }
+            }
+            Widget::Collapsible {
+                expanded,
+                header,
+                body,
+                ..
+            } => {
+                checked(self, *header, *expanded);
+                if let Some(body) = self.get_element_mut(*body) {
+                    let height = match expanded {
+                        true => 100.0,
+                        false => 0.0,
+                    };
+                    body.styles.height.set(Values::container(height, Side::Height));
+                }
+            }
+        }
+    }
+
+    /// Adds the default styles of widgets, see `Gui::set_default_class`
+    fn default_widget_classes(&mut self) {
+        let theme = |name: &str| Colors::Theme(name.to_string(), None);
+        let square = |percent: f32, color: &str, round: bool| {
+            let mut styles = Styles::default();
+            styles.width.set(Values::container(percent, Side::Min));
+            styles.height.set(Values::container(percent, Side::Min));
+            styles.bg_color.set(theme(color));
+            styles.edges_radius.set(match round {
+                true => Values::container(50.0, Side::Min),
+                false => Values::theme("radius"),
+            });
+            styles
+        };
+        let label = || {
+            let mut styles = Styles::default();
+            styles.text_color.set(theme("text"));
+            styles.text_size.set(Values::theme("font_size"));
+            styles
+        };
+        let bar = |height: f32| {
+            let mut styles = Styles::default();
+            styles.height.set(Values::container(height, Side::Height));
+            styles.bg_color.set(theme("border"));
+            styles.edges_radius.set(Values::container(50.0, Side::Height));
+            styles
+        };
+        let fill = || {
+            let mut styles = Styles::default();
+            styles.position.set(Position::center_left().with_pivot(PositionValues::CenterLeft));
+            styles.bg_color.set(theme("accent"));
+            styles.edges_radius.set(Values::container(50.0, Side::Height));
+            styles
+        };
+
+        let mut button = label();
+        button.bg_color.set(theme("surface"));
+        button.edges_radius.set(Values::theme("radius"));
+        button.margin.set(Values::theme("spacing"));
+        button.states.hover_mut().bg_color.set(theme("border"));
+        button.states.active_mut().bg_color.set(theme("accent"));
+        button.states.disabled_mut().text_color.set(theme("text_muted"));
+        self.set_default_class("button", button);
+
+        for class in ["checkbox-label", "toggle-label", "radio-option-label"] {
+            self.set_default_class(class, label());
+        }
+
+        let mut check = square(70.0, "surface", false);
+        check.states.hover_mut().bg_color.set(theme("border"));
+        self.set_default_class("checkbox-box", check);
+        self.set_default_class("checkbox-mark", square(60.0, "text", false));
+        let mut radio = square(70.0, "surface", true);
+        radio.states.hover_mut().bg_color.set(theme("border"));
+        self.set_default_class("radio", radio);
+        self.set_default_class("radio-dot", square(50.0, "text", true));
+
+        let mut track = bar(50.0);
+        track.width.set(Values::container(80.0, Side::Width));
+        self.set_default_class("toggle-track", track);
+        self.set_default_class("toggle-knob", square(100.0, "surface", true));
+
+        let mut checked = Styles::default();
+        checked.bg_color.set(theme("accent"));
+        self.set_default_class("checked", checked);
+
+        self.set_default_class("slider-track", bar(25.0));
+        self.set_default_class("slider-fill", fill());
+        self.set_default_class("slider-knob", square(60.0, "accent", true));
+
+        self.set_default_class("progress", bar(100.0));
+        self.set_default_class("progress-fill", fill());
+
+        let mut dropdown = label();
+        dropdown.bg_color.set(theme("surface"));
+        dropdown.edges_radius.set(Values::theme("radius"));
+        dropdown.states.hover_mut().bg_color.set(theme("border"));
+        dropdown.states.focus_mut().bg_color.set(theme("border"));
+        self.set_default_class("dropdown", dropdown);
+        let list = || {
+            let mut styles = Styles::default();
+            styles.bg_color.set(theme("surface"));
+            styles.edges_radius.set(Values::theme("radius"));
+            styles
+        };
+        self.set_default_class("dropdown-list", list());
+        self.set_default_class("list-box", list());
+        self.set_default_class("virtual-list", list());
+        let mut thumb = Styles::default();
+        thumb.width.set(Values::logical(6.0));
+        thumb.bg_color.set(theme("border"));
+        thumb.edges_radius.set(Values::logical(3.0));
+        self.set_default_class("virtual-list-thumb", thumb);
+        let mut strip = Styles::default();
+        strip.bg_color.set(theme("surface"));
+        self.set_default_class("tab-strip", strip);
+        let mut divider = Styles::default();
+        divider.bg_color.set(theme("border"));
+        divider.states.hover_mut().bg_color.set(theme("accent"));
+        self.set_default_class("split-divider", divider);
+        let mut header = label();
+        header.bg_color.set(theme("surface"));
+        header.states.hover_mut().bg_color.set(theme("border"));
+        self.set_default_class("collapsible-header", header);
+        let mut preview = Styles::default();
+        preview.bg_color.set(theme("accent"));
+        preview.alpha.set(0.3);
+        self.set_default_class("dock-preview", preview);
+        let mut window = list();
+        window.bg_color.set(theme("background"));
+        self.set_default_class("window", window);
+        let mut title = Styles::default();
+        title.bg_color.set(theme("surface"));
+        self.set_default_class("window-title", title);
+        self.set_default_class("window-title-text", label());
+        let mut grip = square(100.0, "border", false);
+        grip.width.set(Values::logical(12.0));
+        grip.height.set(Values::logical(12.0));
+        self.set_default_class("window-grip", grip);
+        for class in [
+            "dropdown-item",
+            "list-item",
+            "virtual-list-row",
+            "tab",
+            "window-button",
+        ] {
+            let mut item = label();
+            item.states.hover_mut().bg_color.set(theme("border"));
+            item.states.focus_mut().bg_color.set(theme("border"));
+            self.set_default_class(class, item);
+        }
+    }
+}
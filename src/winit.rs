@@ -49,6 +49,15 @@ pub fn event<Msg: Clone>(gui: &mut crate::Gui<Msg>, event: &WinitWindowEvent) {
             event,
             is_synthetic: _,
         } => {
+            if let (ElementState::Pressed, Key::Character(c)) = (event.state, &event.logical_key) {
+                if gui.input.control_pressed {
+                    if let Some(key) = c.chars().next() {
+                        gui.event(RuguiWindowEvent::Shortcut {
+                            key: key.to_ascii_lowercase(),
+                        });
+                    }
+                }
+            }
             match event.state {
                 ElementState::Pressed => match &event.logical_key {
                     Key::Named(winit::keyboard::NamedKey::Tab) => {
@@ -85,6 +94,10 @@ pub fn event<Msg: Clone>(gui: &mut crate::Gui<Msg>, event: &WinitWindowEvent) {
                 }
             }
         }
+        WinitWindowEvent::Resized(size) => gui.set_size((size.width, size.height)),
+        WinitWindowEvent::ScaleFactorChanged { scale_factor, .. } => {
+            gui.set_scale_factor(*scale_factor as f32)
+        }
         _ => (),
     }
 }